@@ -0,0 +1,14 @@
+use aoc_common::solution::{Part, Solution};
+use day_2023_01::*;
+
+aoc_common::bench_main!();
+
+#[divan::bench]
+fn part1() {
+    Day01::solve(divan::black_box(input()), Part::One).unwrap();
+}
+
+#[divan::bench]
+fn part2() {
+    Day01::solve(divan::black_box(input()), Part::Two).unwrap();
+}
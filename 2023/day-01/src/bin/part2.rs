@@ -0,0 +1,15 @@
+use aoc_common::solution::{Part, Solution};
+use day_2023_01::Day01;
+use miette::Context;
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let file = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
+        .map_err(|e| miette::miette!("Read input file: {e}"))?;
+    let result = Day01::solve(file.as_str(), Part::Two).context("process part 2")?;
+
+    println!("{}", result);
+    Ok(())
+}
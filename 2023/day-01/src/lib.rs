@@ -0,0 +1,94 @@
+use aoc_common::input::Input;
+use aoc_common::solution::Solution;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use thiserror::Error;
+
+pub mod part1;
+pub mod part2;
+
+pub struct Day01;
+
+#[derive(Error, Diagnostic, Debug)]
+#[error("Line {line} has no digit to take a calibration value from")]
+#[diagnostic(help("every line needs at least one digit, written as a numeral or spelled out"))]
+pub struct NoDigitError {
+    pub line: usize,
+    #[source_code]
+    input: NamedSource<String>,
+    #[label("no digit on this line")]
+    pub span: SourceSpan,
+}
+
+/// The lines of the calibration document, each part picks the digits out of them.
+#[derive(Clone, Debug)]
+pub struct Document {
+    pub lines: Vec<CalibrationLine>,
+    source: NamedSource<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalibrationLine {
+    pub text: String,
+    /// Line number in the original document, starting at 1
+    pub number: usize,
+    pub span: SourceSpan,
+}
+
+pub fn parse(input: &str) -> miette::Result<Document> {
+    let input = Input::new(input);
+    let lines = input
+        .lines()
+        .map(|line| CalibrationLine {
+            text: line.text.to_string(),
+            number: line.number,
+            span: line.span(),
+        })
+        .collect();
+    Ok(Document {
+        lines,
+        source: input.source(),
+    })
+}
+
+/// Sum the first and last digit of every line, as found by `digits`.
+pub(crate) fn sum_calibration_values(
+    document: &Document,
+    digits: impl Fn(&str) -> Vec<u32>,
+) -> Result<u32, NoDigitError> {
+    let mut total = 0;
+    for line in &document.lines {
+        let found = digits(&line.text);
+        tracing::trace!("line: '{}' digits: {:?}", line.text, found);
+        match (found.first(), found.last()) {
+            (Some(first), Some(last)) => total += first * 10 + last,
+            _ => {
+                return Err(NoDigitError {
+                    line: line.number,
+                    input: document.source.clone(),
+                    span: line.span,
+                })
+            }
+        }
+    }
+    Ok(total)
+}
+
+impl Solution for Day01 {
+    type Input = Document;
+    type Answer = u32;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day01);
@@ -0,0 +1,39 @@
+use crate::{sum_calibration_values, Document};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &Document) -> miette::Result<u32> {
+    Ok(sum_calibration_values(input, |line| {
+        line.chars()
+            .filter_map(|character| character.to_digit(10))
+            .collect()
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use test_log::test;
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+        assert_eq!(142, process(&parse(input)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_line_without_digit() -> miette::Result<()> {
+        let document = parse("1abc2\nabcone\n")?;
+        let error = crate::sum_calibration_values(&document, |line| {
+            line.chars().filter_map(|ch| ch.to_digit(10)).collect()
+        })
+        .unwrap_err();
+        assert_eq!(2, error.line);
+        assert!(process(&document).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{sum_calibration_values, Document};
+
+const SPELLED_DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &Document) -> miette::Result<u32> {
+    Ok(sum_calibration_values(input, spelled_digits)?)
+}
+
+/// Every digit in the line, numeral or spelled out, in the order they start. Spelled digits may
+/// share letters, so `eightwo` yields both 8 and 2.
+fn spelled_digits(line: &str) -> Vec<u32> {
+    line.char_indices()
+        .filter_map(|(index, character)| {
+            character.to_digit(10).or_else(|| {
+                SPELLED_DIGITS
+                    .iter()
+                    .position(|word| line[index..].starts_with(word))
+                    .map(|position| position as u32 + 1)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+        assert_eq!(281, process(&parse(input)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_overlapping_words() -> miette::Result<()> {
+        for (line, expected) in [
+            ("eightwo", 82),
+            ("oneight", 18),
+            ("twone", 21),
+            ("sevenine", 79),
+            ("threeight2", 32),
+            ("nineeighthree", 93),
+        ] {
+            assert_eq!(expected, process(&parse(line)?)?, "line {line:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_single_digit_counts_twice() -> miette::Result<()> {
+        assert_eq!(77, process(&parse("seven")?)?);
+        assert_eq!(44, process(&parse("xx4yy")?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_ignores_partial_words() -> miette::Result<()> {
+        assert_eq!(33, process(&parse("thre3fou")?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_without_digit_is_reported() -> miette::Result<()> {
+        let document = parse("1abc2\nzero\n3")?;
+        let error = sum_calibration_values(&document, spelled_digits).unwrap_err();
+        assert_eq!(2, error.line);
+        assert_eq!(SourceSpan::from((6, 4)), error.span);
+        Ok(())
+    }
+}
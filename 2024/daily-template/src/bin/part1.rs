@@ -1,14 +0,0 @@
-use {{crate_name}}::part1::process;
-use miette::Context;
-
-#[tracing::instrument]
-fn main() -> miette::Result<()> {
-    tracing_subscriber::fmt::init();
-
-    let file = std::fs::read_to_string("{{project-name}}/input.txt")
-        .map_err(|e| miette::miette!("Read input file: {e}"))?;
-    let result = process(file.as_str()).context("process part 1")?;
-
-    println!("{}", result);
-    Ok(())
-}
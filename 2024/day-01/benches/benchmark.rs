@@ -1,16 +1,31 @@
-use day_01::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_01::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 1000,
+    generate,
+});
+
+/// `size` lines of two five-digit location IDs.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{}   {}\n",
+                rng.range(10000..100000),
+                rng.range(10000..100000)
+            )
+        })
+        .collect()
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+    Day01::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+    Day01::solve(divan::black_box(input()), Part::Two).unwrap();
 }
@@ -1,2 +1,69 @@
+use aoc_common::input::Input;
+use aoc_common::parsers;
+use aoc_common::solution::Solution;
+use miette::Result;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day01;
+
+/// The two columns of location ids
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct Lists {
+    pub lefts: Vec<u32>,
+    pub rights: Vec<u32>,
+}
+
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &str) -> Result<Lists> {
+    let input = Input::new(input);
+    let pairs = parsers::lines(
+        &input,
+        input.lines(),
+        "two location ids like `3   4`",
+        parsers::column_pair::<u32>,
+    )?;
+
+    let (lefts, rights) = pairs.into_iter().unzip();
+    Ok(Lists { lefts, rights })
+}
+
+impl Solution for Day01 {
+    type Input = Lists;
+    type Answer = u64;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day01);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_reports_bad_id() {
+        let error = parse("3   4\n4   x3\n").unwrap_err();
+        assert_eq!((2, SourceSpan::from((10, 2))), parsers::line_error(&error));
+    }
+
+    #[test]
+    fn test_parse_reports_missing_column() {
+        let error = parse("3   4\n4\n").unwrap_err();
+        assert_eq!((2, SourceSpan::from((6, 1))), parsers::line_error(&error));
+    }
+}
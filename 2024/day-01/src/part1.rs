@@ -1,28 +1,24 @@
-use miette::{IntoDiagnostic, Result};
+use miette::Result;
 use std::iter::zip;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> Result<String> {
-    let mut lefts: Vec<u32> = vec![];
-    let mut rights: Vec<u32> = vec![];
+use crate::Lists;
 
-    for line in input.lines() {
-        tracing::trace!("line: '{}'", line);
-        let mut split_line = line.split_whitespace();
-        lefts.push(split_line.next().unwrap().parse().into_diagnostic()?);
-        rights.push(split_line.next().unwrap().parse().into_diagnostic()?);
-    }
+#[tracing::instrument(skip(input))]
+pub fn process(input: &Lists) -> Result<u64> {
+    let mut lefts = input.lefts.clone();
+    let mut rights = input.rights.clone();
 
     lefts.sort();
     rights.sort();
 
-    let distance: u32 = zip(lefts, rights).map(|(l, r)| l.abs_diff(r)).sum();
-    Ok(distance.to_string())
+    let distance: u64 = zip(lefts, rights).map(|(l, r)| l.abs_diff(r) as u64).sum();
+    Ok(distance)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -33,7 +29,7 @@ mod tests {
 1   3
 3   9
 3   3";
-        assert_eq!("11", process(input)?);
+        assert_eq!(11, process(&parse(input)?)?);
         Ok(())
     }
 }
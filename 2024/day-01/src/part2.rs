@@ -1,33 +1,27 @@
 use std::collections::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut lefts: Vec<usize> = vec![];
-    let mut rights: Vec<usize> = vec![];
+use crate::Lists;
 
-    for line in input.lines() {
-        let mut split_line = line.split_whitespace();
-        lefts.push(split_line.next().unwrap().parse().unwrap());
-        rights.push(split_line.next().unwrap().parse().unwrap());
-    }
-
-    let mut counts: HashMap<usize, usize> = HashMap::new();
-    let mut total: usize = 0;
+#[tracing::instrument(skip(input))]
+pub fn process(input: &Lists) -> miette::Result<u64> {
+    let mut counts: HashMap<u32, u64> = HashMap::new();
+    let mut total: u64 = 0;
 
-    for l in lefts {
+    for l in input.lefts.iter() {
         let num_occurences = counts
-            .entry(l)
-            .or_insert_with(|| rights.iter().filter(|x| **x == l).count());
-        total += *num_occurences * l;
+            .entry(*l)
+            .or_insert_with(|| input.rights.iter().filter(|x| *x == l).count() as u64);
+        total += *num_occurences * *l as u64;
         tracing::trace!("Got num_occurences: {} for entry {}", num_occurences, l);
     }
 
-    Ok(total.to_string())
+    Ok(total)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
 
     #[test_log::test]
     fn test_process() -> miette::Result<()> {
@@ -37,7 +31,7 @@ mod tests {
 1   3
 3   9
 3   3";
-        assert_eq!("31", process(input)?);
+        assert_eq!(31, process(&parse(input)?)?);
         Ok(())
     }
 }
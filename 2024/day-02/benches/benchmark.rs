@@ -1,16 +1,36 @@
-use day_02::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_02::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 1000,
+    generate,
+});
+
+/// `size` reports of five to eight levels, mostly drifting in one direction.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut level = rng.range(10..90) as i64;
+        let direction = if rng.one_in(2) { 1 } else { -1 };
+        let levels: Vec<String> = (0..rng.range(5..9))
+            .map(|_| {
+                level += direction * rng.range(0..4) as i64;
+                level.to_string()
+            })
+            .collect();
+        input += &levels.join(" ");
+        input.push('\n');
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+    Day02::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+    Day02::solve(divan::black_box(input()), Part::Two).unwrap();
 }
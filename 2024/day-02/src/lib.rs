@@ -1,2 +1,54 @@
+use aoc_common::input::Input;
+use aoc_common::parsers;
+use aoc_common::solution::Solution;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day02;
+
+/// One line of levels per report
+pub type Reports = Vec<Vec<isize>>;
+
+pub fn parse(input: &str) -> miette::Result<Reports> {
+    let input = Input::new(input);
+    Ok(parsers::lines(
+        &input,
+        input.lines(),
+        "a report of levels like `7 6 4 2 1`",
+        parsers::integers,
+    )?)
+}
+
+impl Solution for Day02 {
+    type Input = Reports;
+    type Answer = usize;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day02);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_reports_bad_level() {
+        let error = parse("7 6 4\r\n1 2 -\r\n").unwrap_err();
+        assert_eq!((2, SourceSpan::from((11, 1))), parsers::line_error(&error));
+    }
+}
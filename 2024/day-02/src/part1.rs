@@ -1,33 +1,28 @@
+use crate::Reports;
+
 const BOUNDS: std::ops::RangeInclusive<isize> = 1..=3;
 
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &Reports) -> miette::Result<usize> {
     let n: usize = input
-        .lines()
-        .map(|line| {
-            tracing::trace!("Line: '{}'", line);
-            let nums: Vec<isize> = line
-                .split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect();
-
-            if is_line_safe(nums) {
+        .iter()
+        .filter(|nums| {
+            let safe = is_line_safe(nums);
+            if safe {
                 tracing::trace!("Line Safe");
-                1
-            } else {
-                0
             }
+            safe
         })
-        .sum();
+        .count();
 
-    Ok(n.to_string())
+    Ok(n)
 }
 
-pub fn is_line_safe(nums: Vec<isize>) -> bool {
+pub fn is_line_safe(nums: &[isize]) -> bool {
     let mut is_increasing: Option<bool> = None;
     let mut last_num: Option<isize> = None;
     let mut line_safe: bool = true;
 
-    for num in nums {
+    for &num in nums {
         if last_num.is_none() {
             last_num = Some(num);
             continue;
@@ -63,6 +58,7 @@ pub fn is_line_safe(nums: Vec<isize>) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
 
     #[test_log::test]
     fn test_process() -> miette::Result<()> {
@@ -72,14 +68,14 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        assert_eq!("2", process(input)?);
+        assert_eq!(2, process(&parse(input)?)?);
         Ok(())
     }
 
     #[test_log::test]
     fn test_process_with_initial_diff() -> miette::Result<()> {
         let input = "12 6 4 2 1";
-        assert_eq!("0", process(input)?);
+        assert_eq!(0, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,18 +1,14 @@
-use crate::part1;
+use crate::{part1, Reports};
 
 // #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &Reports) -> miette::Result<usize> {
     let n: usize = input
-        .lines()
-        .map(|line| {
-            tracing::debug!("Line: {:?}", line);
-            let nums: Vec<isize> = line
-                .split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect();
+        .iter()
+        .map(|nums| {
+            tracing::debug!("Line: {:?}", nums);
 
-            if part1::is_line_safe(nums.clone()) {
-                tracing::trace!("Line Safe: {:?}", line);
+            if part1::is_line_safe(nums) {
+                tracing::trace!("Line Safe: {:?}", nums);
                 return 1;
             }
 
@@ -21,7 +17,7 @@ pub fn process(input: &str) -> miette::Result<String> {
                 let mut removed_one = nums.clone();
                 removed_one.remove(index);
                 tracing::trace!("Testing Line Variant: {:?}", removed_one.clone());
-                if part1::is_line_safe(removed_one) {
+                if part1::is_line_safe(&removed_one) {
                     tracing::trace!("Safe Line Variant");
                     has_save_variant += 1;
                     break; // This is critical as we only want to count one safe variant per line
@@ -31,12 +27,13 @@ pub fn process(input: &str) -> miette::Result<String> {
         })
         .sum();
 
-    Ok(n.to_string())
+    Ok(n)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
 
     #[test_log::test]
     fn test_process() -> miette::Result<()> {
@@ -46,7 +43,7 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        assert_eq!("4", process(input)?);
+        assert_eq!(4, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,16 +1,43 @@
-use day_03::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_03::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 800,
+    generate,
+});
+
+/// `size` fragments of corrupted memory, mixing real instructions with near misses.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    const NOISE: [&str; 8] = [
+        "x",
+        "mul[",
+        "mul(4*",
+        "!@^",
+        "what()",
+        "mul ( 2 , 4 )",
+        ")",
+        "from()",
+    ];
+    let mut input = String::new();
+    for _ in 0..size {
+        match rng.range(0..6) {
+            0 => input += "do()",
+            1 => input += "don't()",
+            2 | 3 => input += &format!("mul({},{})", rng.range(1..1000), rng.range(1..1000)),
+            _ => input += *rng.choose(&NOISE),
+        }
+    }
+    input.push('\n');
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+    Day03::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+    Day03::solve(divan::black_box(input()), Part::Two).unwrap();
 }
@@ -1,2 +1,71 @@
+use aoc_common::solution::Solution;
+use miette::miette;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, anychar},
+    combinator::value,
+    multi::{many1, many_till},
+    sequence::{delimited, separated_pair},
+    IResult, Parser,
+};
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day03;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+pub fn parse(input: &str) -> miette::Result<Vec<Instruction>> {
+    let (_input, instructions) =
+        parse_instructions(input).map_err(|e| miette!("parse failed {}", e))?;
+    Ok(instructions)
+}
+
+fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    many1(many_till(anychar, parse_instruction_or_mul).map(|(_remaining, res)| res))(input)
+}
+
+fn parse_instruction_or_mul(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        value(Instruction::Dont, tag("don't()")),
+        value(Instruction::Do, tag("do()")),
+        parsemul,
+    ))(input)
+}
+
+fn parsemul(input: &str) -> IResult<&str, Instruction> {
+    let (input, _) = tag("mul")(input)?;
+    let (input, pair) = delimited(
+        tag("("),
+        separated_pair(complete::u32, tag(","), complete::u32),
+        tag(")"),
+    )(input)?;
+    Ok((input, Instruction::Mul(pair.0, pair.1)))
+}
+
+impl Solution for Day03 {
+    type Input = Vec<Instruction>;
+    type Answer = u32;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day03);
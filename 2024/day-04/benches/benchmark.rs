@@ -1,16 +1,28 @@
-use day_04::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_04::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 140,
+    generate,
+});
+
+/// Square word search of side `size` drawn from the letters of XMAS.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        input.extend((0..size).map(|_| *rng.choose(&['X', 'M', 'A', 'S'])));
+        input.push('\n');
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day04::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day04::solve(divan::black_box(input()), Part::Two).unwrap();
 }
@@ -1,2 +1,31 @@
+use aoc_common::grid::{Grid, YAxis};
+use aoc_common::solution::Solution;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day04;
+
+pub fn parse(input: &str) -> miette::Result<Grid<char>> {
+    Ok(Grid::parse(input, YAxis::Down, |letter| letter)?)
+}
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+    type Answer = usize;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day04);
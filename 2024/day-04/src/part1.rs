@@ -1,62 +1,31 @@
-use std::collections::HashMap;
-
+use aoc_common::direction::Direction;
+use aoc_common::grid::Grid;
 use tracing::trace;
 
-type Point = (i32, i32);
-
-const OFFSETS: [[Point; 3]; 8] = [
-    [(1, 0), (2, 0), (3, 0)],
-    [(-1, 0), (-2, 0), (-3, 0)],
-    [(0, 1), (0, 2), (0, 3)],
-    [(0, -1), (0, -2), (0, -3)],
-    [(1, 1), (2, 2), (3, 3)],
-    [(-1, -1), (-2, -2), (-3, -3)],
-    [(1, -1), (2, -2), (3, -3)],
-    [(-1, 1), (-2, 2), (-3, 3)],
-];
-
-pub fn process(input: &str) -> miette::Result<String> {
-    let matrix_map: HashMap<Point, char> = HashMap::from_iter(
-        input
-            .lines()
-            .skip_while(|line| line.is_empty())
-            .enumerate()
-            .flat_map(|(row_index, line)| {
-                line.chars().enumerate().map(move |(column_index, letter)| {
-                    ((column_index as i32, row_index as i32), letter)
-                })
-            })
-            .collect::<Vec<(Point, char)>>(),
-    );
-
-    let other_letters: Vec<char> = "MAS".chars().collect();
-    let total_hits: usize = matrix_map
-        .iter()
-        .filter(|(_position, value)| **value == 'X')
-        .map(|(position, _value)| {
-            trace!("Found {} at position {:?}", _value, position,);
-            OFFSETS
-                .iter()
-                .map(|offset_points| {
-                    offset_points
-                        .iter()
-                        .map(|offset| {
-                            matrix_map.get(&(position.0 + offset.0, position.1 + offset.1))
-                        })
-                        .enumerate()
-                        .all(|(index, value)| other_letters.get(index) == value)
+pub fn process(grid: &Grid<char>) -> miette::Result<usize> {
+    let total_hits: usize = grid
+        .positions(|value| *value == 'X')
+        .map(|position| {
+            trace!("Found X at position {:?}", position);
+            Direction::all()
+                .filter(|direction| {
+                    let mut next_position = position;
+                    "MAS".chars().all(|letter| {
+                        next_position = grid.step(next_position, *direction);
+                        grid.get(next_position) == Some(&letter)
+                    })
                 })
-                .filter(|b| *b)
                 .count()
         })
         .sum();
 
-    Ok(total_hits.to_string())
+    Ok(total_hits)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -73,7 +42,7 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX
 ";
-        assert_eq!("18", process(input)?);
+        assert_eq!(18, process(&parse(input)?)?);
         Ok(())
     }
 }
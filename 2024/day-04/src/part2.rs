@@ -1,59 +1,32 @@
-use std::collections::HashMap;
-
+use aoc_common::direction::Direction;
+use aoc_common::grid::Grid;
 use tracing::trace;
 
-type Point = (i32, i32);
-
-const OFFSETS: [[Point; 2]; 4] = [
-    [(1, 1), (-1, -1)],
-    [(1, -1), (-1, 1)],
-    [(-1, -1), (1, 1)],
-    [(-1, 1), (1, -1)],
-];
-
-pub fn process(input: &str) -> miette::Result<String> {
-    let matrix_map: HashMap<Point, char> = HashMap::from_iter(
-        input
-            .lines()
-            .skip_while(|line| line.is_empty())
-            .enumerate()
-            .flat_map(|(row_index, line)| {
-                line.chars().enumerate().map(move |(column_index, letter)| {
-                    ((column_index as i32, row_index as i32), letter)
-                })
-            })
-            .collect::<Vec<(Point, char)>>(),
-    );
-
-    let other_letters: Vec<char> = "MS".chars().collect();
-    let total_hits: usize = matrix_map
-        .iter()
-        .filter(|(_position, value)| **value == 'A')
-        .filter(|(position, _value)| {
-            trace!("Found {} at position {:?}", _value, position,);
-            OFFSETS
+pub fn process(grid: &Grid<char>) -> miette::Result<usize> {
+    let total_hits: usize = grid
+        .positions(|value| *value == 'A')
+        .filter(|position| {
+            trace!("Found A at position {:?}", position);
+            // Each arm of the X reads "MAS" through the centre, so look for an M on one side
+            // and an S directly opposite
+            Direction::DIAGONAL
                 .iter()
-                .map(|point_offsets| {
-                    point_offsets
-                        .iter()
-                        .map(|offset| {
-                            matrix_map.get(&(position.0 + offset.0, position.1 + offset.1))
-                        })
-                        .enumerate()
-                        .all(|(index, value)| other_letters.get(index) == value)
+                .filter(|direction| {
+                    grid.get(grid.step(*position, **direction)) == Some(&'M')
+                        && grid.get(grid.step(*position, direction.reverse())) == Some(&'S')
                 })
-                .filter(|b| *b)
                 .count()
                 == 2
         })
         .count();
 
-    Ok(total_hits.to_string())
+    Ok(total_hits)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -70,7 +43,7 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX
 ";
-        assert_eq!("9", process(input)?);
+        assert_eq!(9, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,16 +1,42 @@
-use day_05::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_05::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 200,
+    generate,
+});
+
+/// Rules ordering 49 pages in full, followed by `size` updates of odd length.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut pages: Vec<u64> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut input = String::new();
+    for (index, before) in pages.iter().enumerate() {
+        for after in &pages[index + 1..] {
+            input += &format!("{before}|{after}\n");
+        }
+    }
+    input.push('\n');
+    for _ in 0..size {
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(rng.range(2..12) as usize * 2 + 1);
+        let update: Vec<String> = update.iter().map(u64::to_string).collect();
+        input += &update.join(",");
+        input.push('\n');
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day05::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day05::solve(divan::black_box(input()), Part::Two).unwrap();
 }
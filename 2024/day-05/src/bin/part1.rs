@@ -1,13 +1,14 @@
-use day_05::part1::process;
+use aoc_common::solution::{Part, Solution};
+use day_2024_05::Day05;
 use miette::Context;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
-    let file = std::fs::read_to_string("day-05/input.txt")
+    let file = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
         .map_err(|e| miette::miette!("Read input file: {e}"))?;
-    let result = process(file.as_str()).context("process part 1")?;
+    let result = Day05::solve(file.as_str(), Part::One).context("process part 1")?;
 
     println!("{}", result);
     Ok(())
@@ -1,2 +1,140 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::input::Input;
+use aoc_common::parsers;
+use aoc_common::solution::Solution;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use thiserror::Error;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day05;
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct Manual {
+    // NOTE: Rules is a map of entries to collections of elements which should be after that
+    // that element, allowing easy sorting
+    pub rules: HashMap<usize, HashSet<usize>>,
+    pub updates: Vec<Vec<usize>>,
+}
+
+/// The manual isn't exactly ordering rules and updates separated by a blank line.
+#[derive(Error, Diagnostic, Debug)]
+pub enum SectionError {
+    #[error("No updates after the ordering rules")]
+    #[diagnostic(help("separate the ordering rules from the updates with a blank line"))]
+    MissingUpdates {
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("expected a blank line and updates after this")]
+        span: Option<SourceSpan>,
+    },
+    #[error("Unexpected section after the updates")]
+    #[diagnostic(help("the manual has only ordering rules and updates"))]
+    ExtraSection {
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("not part of the manual")]
+        span: SourceSpan,
+    },
+}
+
+pub fn parse(input: &str) -> miette::Result<Manual> {
+    let input = Input::new(input);
+    let sections: Vec<_> = input.sections().collect();
+    let [rule_section, update_section] = match sections[..] {
+        [rules, updates] => [rules, updates],
+        [_, _, extra, ..] => {
+            return Err(SectionError::ExtraSection {
+                input: input.source(),
+                span: extra.span(),
+            }
+            .into())
+        }
+        _ => {
+            return Err(SectionError::MissingUpdates {
+                input: input.source(),
+                span: sections.first().map(|rules| rules.span()),
+            }
+            .into())
+        }
+    };
+
+    let mut rules: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for (left, right) in parsers::lines(
+        &input,
+        rule_section.lines(),
+        "an ordering rule like `47|53`",
+        parsers::rule,
+    )? {
+        rules.entry(left).or_default().insert(right);
+    }
+
+    let updates = parsers::lines(
+        &input,
+        update_section.lines(),
+        "an update like `75,47,61`",
+        parsers::comma_list,
+    )?;
+
+    Ok(Manual { rules, updates })
+}
+
+impl Solution for Day05 {
+    type Input = Manual;
+    type Answer = usize;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day05);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_reports_bad_page() {
+        let error = parse("47|53\n97|13\n\n75,47,6l,53\n").unwrap_err();
+        assert_eq!((4, SourceSpan::from((19, 2))), parsers::line_error(&error));
+    }
+
+    #[test]
+    fn test_parse_requires_updates() {
+        let error = parse("47|53\n97|13\n75,47\n").unwrap_err();
+        let Some(SectionError::MissingUpdates { span, .. }) = error.downcast_ref() else {
+            panic!("expected missing updates, got {error:?}");
+        };
+        assert_eq!(Some(SourceSpan::from((0, 17))), *span);
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_extra_section() {
+        let error = parse("47|53\n\n75,47\n\n61,13\n").unwrap_err();
+        let Some(SectionError::ExtraSection { span, .. }) = error.downcast_ref() else {
+            panic!("expected an extra section, got {error:?}");
+        };
+        assert_eq!(SourceSpan::from((14, 5)), *span);
+    }
+
+    #[test]
+    fn test_parse_reports_bad_rule() {
+        let error = parse("47|53\n97-13\n\n75,47\n").unwrap_err();
+        assert_eq!((2, SourceSpan::from((6, 5))), parsers::line_error(&error));
+    }
+}
@@ -1,31 +1,12 @@
-use std::collections::{HashMap, HashSet};
-
 use tracing::trace;
 
-pub fn process(input: &str) -> miette::Result<String> {
-    // NOTE: Rules is a map of entries to collections of elements which should be after that
-    // that element, allowing easy sorting
-    let mut rules: HashMap<usize, HashSet<usize>> = HashMap::new();
-
-    for line in input.lines().filter(|l| l.contains("|")) {
-        let mut nums = line.split("|");
-        let left = nums.next().unwrap().parse().unwrap();
-        let right = nums.next().unwrap().parse().unwrap();
-        rules.entry(left).or_default().insert(right);
-    }
+use crate::Manual;
 
-    let updates: Vec<Vec<usize>> = input
-        .lines()
-        .skip(rules.len())
-        .filter(|l| l.contains(","))
-        .map(|line| {
-            line.split(",")
-                .map(|num| num.parse::<usize>().unwrap())
-                .collect()
-        })
-        .collect();
+pub fn process(input: &Manual) -> miette::Result<usize> {
+    let rules = &input.rules;
 
-    let result: usize = updates
+    let result: usize = input
+        .updates
         .iter()
         .filter(|update| {
             update.is_sorted_by(|a, b| rules.get(a).is_some_and(|pages| pages.contains(b)))
@@ -37,12 +18,13 @@ pub fn process(input: &str) -> miette::Result<String> {
         })
         .sum();
 
-    Ok(result.to_string())
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -77,7 +59,14 @@ mod tests {
 61,13,29
 97,13,75,29,47
 ";
-        assert_eq!("143", process(input)?);
+        assert_eq!(143, process(&parse(input)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_crlf_input() -> miette::Result<()> {
+        let input = "47|53  \r\n97|13\r\n97|47\r\n\r\n97,47,53\r\n53,47,97  \r\n97,13\r\n";
+        assert_eq!(47 + 13, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,16 +1,57 @@
-use day_06::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_06::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 130,
+    generate,
+});
+
+/// Square lab of side `size` with scattered obstructions and the guard near the middle. Labs
+/// where the guard loops without an extra obstruction have no part 2 answer, so those are
+/// drawn again.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    loop {
+        let input = draw(size, rng);
+        let lab = parse(&input).expect("generated labs parse");
+        if let trajectory::End::Exit { .. } = lab.trajectory(&lab.guards[0], None).end {
+            return input;
+        }
+    }
+}
+
+fn draw(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            input.push(match (x, y) {
+                _ if (x, y) == (size / 2, size / 2) => '^',
+                _ if rng.one_in(20) => '#',
+                _ => '.',
+            });
+        }
+        input.push('\n');
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day06::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+mod part2 {
+    use super::*;
+
+    #[divan::bench]
+    fn naive(bencher: divan::Bencher) {
+        let lab = Day06::parse(input()).unwrap();
+        bencher.bench(|| day_2024_06::part2::naive(divan::black_box(&lab)).unwrap());
+    }
+
+    #[divan::bench]
+    fn jump(bencher: divan::Bencher) {
+        let lab = Day06::parse(input()).unwrap();
+        bencher.bench(|| day_2024_06::part2::process(divan::black_box(&lab)).unwrap());
+    }
 }
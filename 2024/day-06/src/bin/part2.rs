@@ -1,13 +1,14 @@
-use day_06::part2::process;
+use aoc_common::solution::{Part, Solution};
+use day_2024_06::Day06;
 use miette::Context;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
-    let file = std::fs::read_to_string("day-06/input.txt")
+    let file = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
         .map_err(|e| miette::miette!("Read input file: {e}"))?;
-    let result = process(file.as_str()).context("process part 2")?;
+    let result = Day06::solve(file.as_str(), Part::Two).context("process part 2")?;
 
     println!("{}", result);
     Ok(())
@@ -0,0 +1,21 @@
+use day_2024_06::parse;
+use miette::Context;
+
+/// Print every guard's trajectory as JSON lines, for the input given as the first argument or
+/// the day's input.txt.
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt").to_string());
+    let file = std::fs::read_to_string(&path)
+        .map_err(|e| miette::miette!("Read input file {path}: {e}"))?;
+    let lab = parse(&file).context("parse lab")?;
+
+    for guard in &lab.guards {
+        println!("{}", lab.trajectory(guard, None).to_json());
+    }
+    Ok(())
+}
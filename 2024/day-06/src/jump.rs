@@ -0,0 +1,150 @@
+use aoc_common::grid::YAxis;
+use glam::IVec2;
+
+use crate::{GuardLocation, Lab, Tile};
+
+/// The obstructions of every row and column in sorted order, so the guard can jump straight to
+/// its next turn with a binary search instead of walking there a tile at a time.
+#[derive(Clone, Debug)]
+pub struct JumpTable {
+    y_axis: YAxis,
+    /// X of each obstruction, per row
+    rows: Vec<Vec<i32>>,
+    /// Y of each obstruction, per column
+    columns: Vec<Vec<i32>>,
+}
+
+impl JumpTable {
+    pub fn new(lab: &Lab) -> Self {
+        let mut rows = vec![vec![]; lab.grid.height()];
+        let mut columns = vec![vec![]; lab.grid.width()];
+        for position in lab.grid.positions(|tile| *tile == Tile::Obstruction) {
+            rows[position.y as usize].push(position.x);
+            columns[position.x as usize].push(position.y);
+        }
+        for line in rows.iter_mut().chain(columns.iter_mut()) {
+            line.sort_unstable();
+        }
+
+        JumpTable {
+            y_axis: lab.grid.y_axis(),
+            rows,
+            columns,
+        }
+    }
+
+    /// Where the guard next turns, already facing its new direction, or `None` if it walks off
+    /// the map first. `extra` is one more obstruction to treat as part of the map.
+    pub fn jump(&self, guard: &GuardLocation, extra: Option<IVec2>) -> Option<GuardLocation> {
+        let offset = guard.direction.offset(self.y_axis);
+        let horizontal = offset.x != 0;
+        // NOTE: Work in coordinates along the guard's line, with `step` the sign of its heading
+        let along = |position: IVec2| if horizontal { position.x } else { position.y };
+        let across = |position: IVec2| if horizontal { position.y } else { position.x };
+        let (line, step) = if horizontal {
+            (&self.rows[guard.position.y as usize], offset.x)
+        } else {
+            (&self.columns[guard.position.x as usize], offset.y)
+        };
+        let start = along(guard.position);
+
+        let ahead = if step > 0 {
+            line.get(line.partition_point(|obstruction| *obstruction <= start))
+        } else {
+            line[..line.partition_point(|obstruction| *obstruction < start)].last()
+        };
+        let extra_ahead = extra
+            .filter(|extra| across(*extra) == across(guard.position))
+            .map(along)
+            .filter(|extra| (extra - start) * step > 0);
+        let obstruction = ahead
+            .copied()
+            .into_iter()
+            .chain(extra_ahead)
+            .min_by_key(|obstruction| (obstruction - start) * step)?;
+
+        let stop = obstruction - step;
+        Some(GuardLocation {
+            position: if horizontal {
+                IVec2::new(stop, guard.position.y)
+            } else {
+                IVec2::new(guard.position.x, stop)
+            },
+            direction: guard.direction.turn_right(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use aoc_common::direction::Direction;
+    use test_log::test;
+
+    const LAB: &str = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn test_jump_matches_walking() -> miette::Result<()> {
+        let lab = parse(LAB)?;
+        let jumps = JumpTable::new(&lab);
+        let mut guard = lab.guards[0].clone();
+        let mut walked = lab.patrol(&guard, None).filter(|location| {
+            let turned = location.direction != guard.direction;
+            guard = location.clone();
+            turned
+        });
+        let mut jumped = lab.guards[0].clone();
+        while let Some(next) = jumps.jump(&jumped, None) {
+            assert_eq!(walked.next(), Some(next.clone()));
+            jumped = next;
+        }
+        assert_eq!(None, walked.next());
+        Ok(())
+    }
+
+    #[test]
+    fn test_jump_stops_at_extra_obstruction() -> miette::Result<()> {
+        let lab = parse(LAB)?;
+        let jumps = JumpTable::new(&lab);
+        let east = GuardLocation {
+            position: IVec2::new(4, 8),
+            direction: Direction::East,
+        };
+        let turned_at = |x| GuardLocation {
+            position: IVec2::new(x, 8),
+            direction: Direction::South,
+        };
+        assert_eq!(Some(turned_at(8)), jumps.jump(&east, None));
+        assert_eq!(
+            Some(turned_at(6)),
+            jumps.jump(&east, Some(IVec2::new(7, 8)))
+        );
+        assert_eq!(
+            Some(turned_at(8)),
+            jumps.jump(&east, Some(IVec2::new(2, 8)))
+        );
+        assert_eq!(
+            Some(turned_at(8)),
+            jumps.jump(&east, Some(IVec2::new(7, 7)))
+        );
+
+        let off_the_map = GuardLocation {
+            position: IVec2::new(4, 3),
+            direction: Direction::South,
+        };
+        assert_eq!(None, jumps.jump(&off_the_map, None));
+        Ok(())
+    }
+}
@@ -1,2 +1,277 @@
+use aoc_common::direction::Direction;
+use aoc_common::grid::{Grid, YAxis};
+use aoc_common::solution::{Part, Solution};
+use aoc_common::{input, parsers};
+use glam::IVec2;
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub mod jump;
 pub mod part1;
 pub mod part2;
+pub mod trajectory;
+
+pub struct Day06;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lab {
+    pub grid: Grid<Tile>,
+    /// Every guard in reading order, which is the order they are simulated in
+    pub guards: Vec<GuardLocation>,
+}
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum ParseError {
+    #[error("No guard in the lab map")]
+    #[diagnostic(help("mark where a guard starts with `^`, `>`, `v` or `<`"))]
+    NoGuard {
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("no guard in here")]
+        span: SourceSpan,
+    },
+}
+
+/// A guard that never leaves the map even without an extra obstruction, which leaves nothing
+/// for part 2 to count.
+#[derive(Error, Diagnostic, Debug, PartialEq, Eq)]
+#[error("Guard starting at {} facing {:?} already patrols in a loop", .guard.position, .guard.direction)]
+#[diagnostic(help("part 2 needs every guard to walk off the map on its own"))]
+pub struct LoopingGuardError {
+    pub guard: GuardLocation,
+}
+
+pub fn parse(input: &str) -> miette::Result<Lab> {
+    // NOTE: Y axis points up so that X-Y coords make sense
+    let grid = parsers::grid(
+        input,
+        YAxis::Up,
+        "a lab map of `.`, `#` and guards facing `^`, `>`, `v` or `<`",
+        |ch| match ch {
+            '#' => Some(Tile::Obstruction),
+            '^' | '>' | 'v' | '<' => Some(Tile::Guard(
+                Direction::try_from(ch).expect("guard markers are directions"),
+            )),
+            '.' => Some(Tile::Empty),
+            _ => None,
+        },
+    )?;
+
+    let guards: Vec<GuardLocation> = grid
+        .iter()
+        .filter_map(|(position, tile)| match tile {
+            Tile::Guard(direction) => Some(GuardLocation {
+                position,
+                direction: *direction,
+            }),
+            _ => None,
+        })
+        .collect();
+    if guards.is_empty() {
+        return Err(ParseError::NoGuard {
+            input: input::source(input),
+            span: (0, input.len()).into(),
+        }
+        .into());
+    }
+    tracing::debug!("Guard Initial Positions: {:?}", guards);
+
+    Ok(Lab { grid, guards })
+}
+
+impl Lab {
+    /// The guard's next location, turning right instead of moving when the tile ahead is an
+    /// obstruction, or `None` once the guard walks off the map. `extra` is one more obstruction
+    /// to treat as part of the map.
+    pub fn step(&self, guard: &GuardLocation, extra: Option<IVec2>) -> Option<GuardLocation> {
+        let next_position = self.grid.step(guard.position, guard.direction);
+        match self.grid.get(next_position)? {
+            Tile::Obstruction => Some(guard.turned_right()),
+            _ if extra == Some(next_position) => Some(guard.turned_right()),
+            _ => Some(GuardLocation {
+                position: next_position,
+                direction: guard.direction,
+            }),
+        }
+    }
+
+    /// Every location of `guard` from where it starts until it leaves the map. This never ends
+    /// if the guard is stuck in a loop. Other guards don't get in the way.
+    pub fn patrol(
+        &self,
+        guard: &GuardLocation,
+        extra: Option<IVec2>,
+    ) -> impl Iterator<Item = GuardLocation> + '_ {
+        std::iter::successors(Some(guard.clone()), move |guard| self.step(guard, extra))
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Tile {
+    Obstruction,
+    /// Where a guard starts, facing the given way
+    Guard(Direction),
+    Empty,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GuardLocation {
+    pub position: IVec2,
+    pub direction: Direction,
+}
+
+impl GuardLocation {
+    fn turned_right(&self) -> GuardLocation {
+        GuardLocation {
+            position: self.position,
+            direction: self.direction.turn_right(),
+        }
+    }
+}
+
+impl Solution for Day06 {
+    type Input = Lab;
+    type Answer = usize;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+
+    /// Each guard's patrol drawn onto the lab for part 1. Part 2 has nothing to draw, as its
+    /// answer is the obstructions that would trap a guard.
+    fn breakdown(
+        input: &Self::Input,
+        part: Part,
+        _config: &Self::Config,
+    ) -> miette::Result<Vec<String>> {
+        if part == Part::Two {
+            return Ok(vec![]);
+        }
+        let mut lines = vec![];
+        for guard in &input.guards {
+            let trajectory = input.trajectory(guard, None);
+            lines.push(format!(
+                "Guard starting at {} facing {:?}: {:?}",
+                guard.position, guard.direction, trajectory.end
+            ));
+            lines.extend(trajectory.render(input).lines().map(str::to_string));
+        }
+        Ok(lines)
+    }
+}
+
+aoc_common::verify_answers!(Day06);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_breakdown_draws_each_patrol() -> miette::Result<()> {
+        let lab = parse("#...\n.^.<\n")?;
+        let lines = Day06::breakdown(&lab, Part::One, &())?;
+        assert_eq!(
+            vec![
+                "Guard starting at [1, 0] facing North: Exit { edge: North }",
+                "#|..",
+                ".^.<",
+                "Guard starting at [3, 0] facing West: Exit { edge: West }",
+                "#...",
+                "-^-<",
+            ],
+            lines
+        );
+        assert!(Day06::breakdown(&lab, Part::Two, &())?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_guard_position() -> miette::Result<()> {
+        let input = "
+..........
+....^.....
+";
+        let Lab { guards, .. } = parse(input)?;
+        assert_eq!(IVec2::new(4, 0), guards[0].position);
+        assert_eq!(Direction::North, guards[0].direction);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_guard_position_non_zero_row() -> miette::Result<()> {
+        let input = "
+....^.....
+..........
+";
+        let Lab { guards, .. } = parse(input)?;
+        assert_eq!(IVec2::new(4, 1), guards[0].position);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_reports_unexpected_tile() {
+        let input = "
+..#.
+.^.X
+";
+        let error = parse(input).unwrap_err();
+        assert_eq!(('X', SourceSpan::from((9, 1))), parsers::cell_error(&error));
+    }
+
+    #[test]
+    fn test_parse_guard_position_and_obstruction() -> miette::Result<()> {
+        let input = "....^...#.";
+        let Lab { grid, guards } = parse(input)?;
+        assert_eq!(IVec2::new(4, 0), guards[0].position);
+        assert_eq!(
+            vec![IVec2::new(8, 0)],
+            grid.positions(|tile| *tile == Tile::Obstruction)
+                .collect::<Vec<IVec2>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_guard_orientations() -> miette::Result<()> {
+        let input = "
+.>..
+#..v
+<..^
+";
+        let Lab { guards, .. } = parse(input)?;
+        assert_eq!(
+            vec![
+                (IVec2::new(1, 2), Direction::East),
+                (IVec2::new(3, 1), Direction::South),
+                (IVec2::new(0, 0), Direction::West),
+                (IVec2::new(3, 0), Direction::North),
+            ],
+            guards
+                .iter()
+                .map(|guard| (guard.position, guard.direction))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_reports_missing_guard() {
+        let input = "..#.\n....\n";
+        let error = parse(input).unwrap_err();
+        let Some(ParseError::NoGuard { span, .. }) = error.downcast_ref() else {
+            panic!("expected a missing guard error, got {error:?}");
+        };
+        assert_eq!(SourceSpan::from((0, 10)), *span);
+    }
+}
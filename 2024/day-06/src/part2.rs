@@ -1,93 +1,168 @@
 use std::collections::HashSet;
 
-use glam::UVec2;
-
-use crate::part1;
-
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (bounds, original_guard, obstructions) = part1::parse_input(input);
-    let mut visited_positions: HashSet<UVec2> = HashSet::from([original_guard.position]);
-
-    // Collect all guard positions on the original path - similar to part1 but with directions
-    // included also
-    let mut guard = original_guard.clone();
-    while !part1::is_exiting_bounds(&guard, &bounds) {
-        let next_position = part1::get_next_position(&guard);
-
-        if obstructions.contains(&next_position) {
-            guard.direction = guard.direction.pivot();
-        } else {
-            guard.position = next_position;
-            visited_positions.insert(guard.position);
+use aoc_common::grid::Grid;
+use glam::IVec2;
+
+use crate::jump::JumpTable;
+use crate::trajectory::End;
+use crate::{GuardLocation, Lab, LoopingGuardError};
+
+/// Number of positions where one more obstruction traps at least one of the guards in a loop.
+/// No guard's starting position may be used, and every guard has to leave the map without one.
+#[tracing::instrument(skip(lab))]
+pub fn process(lab: &Lab) -> miette::Result<usize> {
+    let jumps = JumpTable::new(lab);
+    let mut states = StateSet::new(&lab.grid);
+    let mut walked = StateSet::new(&lab.grid);
+    let mut traps = vec![false; lab.grid.width() * lab.grid.height()];
+
+    for start in &lab.guards {
+        let mut tried = vec![false; traps.len()];
+        for guard in &lab.guards {
+            tried[index(&lab.grid, guard.position)] = true;
         }
-    }
 
-    // For each visited position by the guard, check if adding an obstruction in the next
-    // position would put the guard in a loop. This can be checked by moving the guard in the
-    // standard fashion and cheching if it crosses the set
-    let results = visited_positions
-        .clone()
-        .iter()
-        .filter(|obstruction_candidate| {
-            let mut guard = original_guard.clone();
-            tracing::info!(
-                "Assessing candidate {:?}, starting pos {:?}",
-                obstruction_candidate,
-                guard
-            );
-
-            // Original guard position is not allowed
-            if obstruction_candidate == &&guard.position {
-                return false;
+        // NOTE: An obstruction only changes the route from the first time the guard would walk
+        // into it, so each candidate is tried once, starting from the location just before it
+        let mut guard = start.clone();
+        walked.clear();
+        while let Some(next) = lab.step(&guard, None) {
+            if !walked.insert(&next) {
+                return Err(LoopingGuardError {
+                    guard: start.clone(),
+                }
+                .into());
             }
-
-            let mut guard_locations: HashSet<part1::GuardLocation> = HashSet::from([guard.clone()]);
-
-            loop {
-                // Standard Guard Movement, including new obstruction candidate position
-                let next_position = part1::get_next_position(&guard);
-                if obstructions.contains(&next_position)
-                    || (&&next_position == obstruction_candidate)
-                {
-                    guard.direction = guard.direction.pivot();
-                    continue;
-                } else {
-                    guard.position = next_position;
+            let candidate = index(&lab.grid, next.position);
+            if next.position != guard.position && !tried[candidate] {
+                tried[candidate] = true;
+                if !traps[candidate] && is_loop(&jumps, &guard, next.position, &mut states) {
+                    tracing::debug!("Candidate {:?} traps {:?}", next.position, start);
+                    traps[candidate] = true;
                 }
+            }
+            guard = next;
+        }
+    }
 
-                // After moving the guard to the next position, check if we are on a previous
-                // position and vector
-                if guard_locations.contains(&guard) {
-                    tracing::debug!(
-                        "Found Loop: {:?}, guard pos {:?}",
-                        obstruction_candidate,
-                        guard
-                    );
-                    break true;
-                }
+    Ok(traps.iter().filter(|trap| **trap).count())
+}
 
-                if part1::is_exiting_bounds(&guard, &bounds) {
-                    tracing::debug!(
-                        "Hit bounds at {:?}, cannot be a Loop: {:?}",
-                        guard,
-                        obstruction_candidate,
-                    );
-                    // Exited bounds - cannot be a loop
-                    break false;
-                }
+/// Whether the guard walking from `start` with an extra obstruction at `candidate` never leaves
+/// the map. Only the locations where the guard turns are recorded, as a loop has to repeat one.
+fn is_loop(
+    jumps: &JumpTable,
+    start: &GuardLocation,
+    candidate: IVec2,
+    states: &mut StateSet,
+) -> bool {
+    states.clear();
+    let mut guard = start.clone();
+    while let Some(next) = jumps.jump(&guard, Some(candidate)) {
+        if !states.insert(&next) {
+            return true;
+        }
+        guard = next;
+    }
+    false
+}
 
-                guard_locations.insert(guard.clone());
+/// Re-walk the whole route a tile at a time for every candidate, remembering every location in a
+/// `HashSet` to spot a repeat, as this day was first solved. Kept to benchmark and check
+/// [`process`] against.
+#[tracing::instrument(skip(lab))]
+pub fn naive(lab: &Lab) -> miette::Result<usize> {
+    // NOTE: An obstruction can only change a guard's route if it is somewhere on one of the
+    // original routes, and the starting positions are not allowed
+    let starts: HashSet<IVec2> = lab.guards.iter().map(|guard| guard.position).collect();
+    let mut candidates: HashSet<IVec2> = HashSet::new();
+    for guard in &lab.guards {
+        let trajectory = lab.trajectory(guard, None);
+        if let End::Loop { .. } = trajectory.end {
+            return Err(LoopingGuardError {
+                guard: guard.clone(),
             }
+            .into());
+        }
+        candidates.extend(
+            trajectory
+                .states
+                .iter()
+                .map(|guard| guard.position)
+                .filter(|position| !starts.contains(position)),
+        );
+    }
+
+    let loops = candidates
+        .iter()
+        .filter(|candidate| {
+            lab.guards.iter().any(|start| {
+                let mut seen: HashSet<GuardLocation> = HashSet::from([start.clone()]);
+                let mut guard = start.clone();
+                while let Some(next) = lab.step(&guard, Some(**candidate)) {
+                    if !seen.insert(next.clone()) {
+                        tracing::debug!("Candidate {:?} traps {:?}", candidate, start);
+                        return true;
+                    }
+                    guard = next;
+                }
+                false
+            })
         })
         .count();
 
-    Ok(results.to_string())
+    Ok(loops)
+}
+
+/// Guard locations seen in one simulation, one bit per position and cardinal direction.
+struct StateSet {
+    width: usize,
+    bits: Vec<u64>,
+    /// Words with bits set, so clearing doesn't touch the whole grid
+    dirty: Vec<usize>,
+}
+
+impl StateSet {
+    fn new<T>(grid: &Grid<T>) -> Self {
+        StateSet {
+            width: grid.width(),
+            bits: vec![0; (grid.width() * grid.height() * 4).div_ceil(64)],
+            dirty: vec![],
+        }
+    }
+
+    /// Record `guard`, returning `false` if it was already recorded.
+    fn insert(&mut self, guard: &GuardLocation) -> bool {
+        let position = guard.position.y as usize * self.width + guard.position.x as usize;
+        // NOTE: Cardinal directions are every other variant, clockwise from North
+        let bit = position * 4 + guard.direction as usize / 2;
+        let (word, mask) = (bit / 64, 1 << (bit % 64));
+        if self.bits[word] & mask != 0 {
+            return false;
+        }
+        if self.bits[word] == 0 {
+            self.dirty.push(word);
+        }
+        self.bits[word] |= mask;
+        true
+    }
+
+    fn clear(&mut self) {
+        for word in self.dirty.drain(..) {
+            self.bits[word] = 0;
+        }
+    }
+}
+
+fn index<T>(grid: &Grid<T>, position: IVec2) -> usize {
+    position.y as usize * grid.width() + position.x as usize
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
+    use aoc_common::bench::Rng;
     use test_log::test;
 
     #[test]
@@ -104,7 +179,56 @@ mod tests {
 #.........
 ......#...
 ";
-        assert_eq!("6", process(input)?);
+        assert_eq!(6, process(&parse(input)?)?);
+        assert_eq!(6, naive(&parse(input)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_jumps_agree_with_naive() -> miette::Result<()> {
+        let mut rng = Rng::new(6);
+        let mut checked = 0;
+        while checked < 50 {
+            let mut input = String::new();
+            for y in 0..12 {
+                for x in 0..12 {
+                    input.push(match (x, y) {
+                        (6, 6) => '^',
+                        _ if rng.one_in(40) => *rng.choose(&['>', 'v', '<']),
+                        _ if rng.one_in(6) => '#',
+                        _ => '.',
+                    });
+                }
+                input.push('\n');
+            }
+            let lab = parse(&input)?;
+            // NOTE: Both solvers refuse labs where a guard loops without an extra obstruction
+            let (Ok(walked), Ok(jumped)) = (naive(&lab), process(&lab)) else {
+                assert!(naive(&lab).is_err() && process(&lab).is_err(), "{input}");
+                continue;
+            };
+            assert_eq!(walked, jumped, "{input}");
+            checked += 1;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_guard_already_looping() -> miette::Result<()> {
+        let input = "
+.#...
+.^..#
+#....
+...#.
+";
+        let lab = parse(input)?;
+        let looping = LoopingGuardError {
+            guard: lab.guards[0].clone(),
+        };
+        for solver in [process, naive] {
+            let error = solver(&lab).unwrap_err();
+            assert_eq!(Some(&looping), error.downcast_ref::<LoopingGuardError>());
+        }
         Ok(())
     }
 }
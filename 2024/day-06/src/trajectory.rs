@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use aoc_common::cycle::{self, Outcome};
+use aoc_common::direction::Direction;
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{GuardLocation, Lab, Tile};
+
+/// Everything one guard does on its patrol, for comparing against the puzzle's worked examples.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trajectory {
+    /// Every location of the guard in order, each one only once, so a looping patrol stops
+    /// just before it repeats itself
+    pub states: Vec<GuardLocation>,
+    /// Positions where the guard turned, in order
+    pub turns: Vec<IVec2>,
+    /// The extra obstruction the guard was simulated with, if any
+    pub extra: Option<IVec2>,
+    pub end: End,
+}
+
+/// How a patrol finishes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum End {
+    /// The guard walked off the map through the edge on this side
+    Exit { edge: Direction },
+    /// The guard repeats `states[start..]` forever
+    Loop { start: usize, length: usize },
+}
+
+impl Lab {
+    /// Follow `guard` until it leaves the map or starts repeating itself.
+    pub fn trajectory(&self, guard: &GuardLocation, extra: Option<IVec2>) -> Trajectory {
+        let mut states = vec![guard.clone()];
+        // NOTE: Hashing steps each state exactly once, so the states seen are exactly the ones
+        // stepped to, the repeated one included
+        let outcome = cycle::hashed(guard.clone(), |guard| {
+            let next = self.step(guard, extra)?;
+            states.push(next.clone());
+            Some(next)
+        });
+        let end = match outcome {
+            Outcome::Terminated { last, .. } => End::Exit {
+                edge: last.direction,
+            },
+            Outcome::Cycle { start, length } => {
+                states.truncate(start + length);
+                End::Loop { start, length }
+            }
+        };
+        // NOTE: A loop also turns where it closes, going from its last state back to its first
+        let closing = match end {
+            End::Loop { start, .. } => {
+                Some([states[states.len() - 1].clone(), states[start].clone()])
+            }
+            End::Exit { .. } => None,
+        };
+        let turns = states
+            .windows(2)
+            .chain(closing.as_ref().map(|pair| pair.as_slice()))
+            .filter(|pair| pair[0].direction != pair[1].direction)
+            .map(|pair| pair[1].position)
+            .collect();
+
+        Trajectory {
+            states,
+            turns,
+            extra,
+            end,
+        }
+    }
+}
+
+impl Trajectory {
+    /// The trajectory as one line of JSON, for plotting or diffing outside this crate.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("trajectories always serialise")
+    }
+
+    /// Draw the patrol onto the lab map the way the puzzle does: `|` for walking up or down, `-`
+    /// for walking across, `+` where both happen or the guard turns, and `O` for the extra
+    /// obstruction. Obstructions and guard starts are drawn as they were in the input.
+    pub fn render(&self, lab: &Lab) -> String {
+        let mut walked: HashMap<IVec2, (bool, bool)> = HashMap::new();
+        for state in &self.states {
+            let (vertical, horizontal) = walked.entry(state.position).or_default();
+            match state.direction {
+                Direction::North | Direction::South => *vertical = true,
+                _ => *horizontal = true,
+            }
+        }
+
+        let mut map = String::new();
+        for (position, tile) in lab.grid.iter() {
+            map.push(match tile {
+                Tile::Obstruction => '#',
+                Tile::Guard(direction) => match direction {
+                    Direction::East => '>',
+                    Direction::South => 'v',
+                    Direction::West => '<',
+                    _ => '^',
+                },
+                Tile::Empty if self.extra == Some(position) => 'O',
+                Tile::Empty => match walked.get(&position) {
+                    Some((true, true)) => '+',
+                    Some((true, false)) => '|',
+                    Some((false, true)) => '-',
+                    _ => '.',
+                },
+            });
+            if position.x as usize == lab.grid.width() - 1 {
+                map.push('\n');
+            }
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use test_log::test;
+
+    const LAB: &str = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn test_render_matches_worked_example() -> miette::Result<()> {
+        let lab = parse(LAB)?;
+        // NOTE: The first of the six loops in the puzzle, with a new obstruction next to the
+        // guard's start
+        let trajectory = lab.trajectory(&lab.guards[0], Some(IVec2::new(3, 3)));
+        let expected = "
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+";
+        assert_eq!(expected.trim_start(), trajectory.render(&lab));
+        assert!(matches!(trajectory.end, End::Loop { start: 0, .. }));
+        assert_eq!(
+            vec![
+                IVec2::new(4, 8),
+                IVec2::new(8, 8),
+                IVec2::new(8, 3),
+                IVec2::new(4, 3)
+            ],
+            trajectory.turns
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_exit_edge_and_json() -> miette::Result<()> {
+        let lab = parse(LAB)?;
+        let trajectory = lab.trajectory(&lab.guards[0], None);
+        assert_eq!(
+            End::Exit {
+                edge: Direction::South
+            },
+            trajectory.end
+        );
+        assert_eq!(
+            Some(IVec2::new(7, 0)),
+            trajectory.states.last().map(|state| state.position)
+        );
+
+        let json = trajectory.to_json();
+        assert!(!json.contains('\n'));
+        assert!(
+            json.contains(r#""end":{"Exit":{"edge":"South"}}"#),
+            "{json}"
+        );
+        let parsed: Trajectory = serde_json::from_str(&json).expect("trajectory deserialises");
+        assert_eq!(trajectory, parsed);
+        Ok(())
+    }
+}
@@ -1,16 +1,97 @@
-use day_07::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_07::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 850,
+    generate,
+});
+
+/// `size` equations of two to nine numbers below 100, about half of them solvable.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let numbers: Vec<u64> = (0..rng.range(2..10)).map(|_| rng.range(1..100)).collect();
+        // NOTE: With numbers below 100 concatenating everything is the largest result, at most
+        // 18 digits, so no combination of operators can overflow a u64
+        let test_value = if rng.one_in(2) {
+            numbers[1..]
+                .iter()
+                .fold(numbers[0], |acc, n| match rng.range(0..3) {
+                    0 => acc + n,
+                    1 => acc * n,
+                    _ => format!("{acc}{n}").parse().expect("fits in u64"),
+                })
+        } else {
+            rng.range(1..1_000_000_000)
+        };
+        let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        input += &format!("{test_value}: {}\n", numbers.join(" "));
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day07::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+mod part2 {
+    use super::*;
+    use day_2024_07::solver::{self, total_calibration};
+
+    #[divan::bench]
+    fn naive(bencher: divan::Bencher) {
+        let equations = Day07::parse(input()).unwrap();
+        bencher.bench(|| {
+            total_calibration(
+                divan::black_box(&equations),
+                Operator::allowed(Part::Two),
+                solver::naive,
+            )
+        });
+    }
+
+    #[divan::bench]
+    fn backward(bencher: divan::Bencher) {
+        let equations = Day07::parse(input()).unwrap();
+        bencher.bench(|| {
+            total_calibration(
+                divan::black_box(&equations),
+                Operator::allowed(Part::Two),
+                solver::backward,
+            )
+        });
+    }
+}
+
+/// Concatenating with digit arithmetic against the string round trip it replaced, both through
+/// the naive solver where concatenation is most of the work.
+mod concat {
+    use super::*;
+    use day_2024_07::solver::{self, total_calibration};
+
+    #[divan::bench]
+    fn strings(bencher: divan::Bencher) {
+        let equations = Day07::parse(input()).unwrap();
+        bencher.bench(|| {
+            total_calibration(
+                divan::black_box(&equations),
+                Operator::allowed(Part::Two),
+                solver::naive_strings,
+            )
+        });
+    }
+
+    #[divan::bench]
+    fn digits(bencher: divan::Bencher) {
+        let equations = Day07::parse(input()).unwrap();
+        bencher.bench(|| {
+            total_calibration(
+                divan::black_box(&equations),
+                Operator::allowed(Part::Two),
+                solver::naive,
+            )
+        });
+    }
 }
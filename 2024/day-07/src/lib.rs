@@ -1,2 +1,174 @@
+use std::fmt;
+
+use aoc_common::input::Input;
+use aoc_common::parsers;
+use aoc_common::solution::{Part, Solution};
+
 pub mod part1;
 pub mod part2;
+pub mod solver;
+
+pub struct Day07;
+
+pub fn parse(input: &str) -> miette::Result<Vec<Equation>> {
+    let input = Input::new(input);
+    let equations = parsers::lines(
+        &input,
+        input.lines(),
+        "an equation like `3267: 81 40 27`",
+        parsers::equation,
+    )?;
+    tracing::trace!("Parse Result: {:?}", equations);
+    Ok(equations
+        .into_iter()
+        .map(|(test_value, numbers)| Equation {
+            test_value,
+            numbers,
+        })
+        .collect())
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Equation {
+    pub test_value: u64,
+    pub numbers: Vec<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Concat,
+}
+
+impl Operator {
+    /// Every operator, part 1 only allows the first two.
+    pub const ALL: [Operator; 3] = [Operator::Add, Operator::Multiply, Operator::Concat];
+
+    /// The operators a part may use.
+    pub fn allowed(part: Part) -> &'static [Operator] {
+        match part {
+            Part::One => &Self::ALL[..2],
+            Part::Two => &Self::ALL,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Multiply => write!(f, "*"),
+            Operator::Concat => write!(f, "||"),
+        }
+    }
+}
+
+impl Equation {
+    /// The equation written out with `operators` between its numbers, such as
+    /// `81 + 40 * 27 = 3267`.
+    pub fn render(&self, operators: &[Operator]) -> String {
+        let mut expression = self.numbers[0].to_string();
+        for (operator, number) in operators.iter().zip(&self.numbers[1..]) {
+            expression += &format!(" {operator} {number}");
+        }
+        format!("{expression} = {}", self.test_value)
+    }
+}
+
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+    type Answer = u64;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+
+    /// One line per equation with a satisfying expression and how many there are, or a note
+    /// that it can't be calibrated.
+    fn breakdown(
+        input: &Self::Input,
+        part: Part,
+        _config: &Self::Config,
+    ) -> miette::Result<Vec<String>> {
+        let operators = Operator::allowed(part);
+        Ok(input
+            .iter()
+            .map(|equation| match solver::assignment(equation, operators) {
+                Some(assignment) => {
+                    let count = solver::count_assignments(equation, operators);
+                    let plural = if count == 1 { "" } else { "s" };
+                    format!(
+                        "{} ({count} assignment{plural})",
+                        equation.render(&assignment)
+                    )
+                }
+                None => format!(
+                    "{}: {} (no assignment)",
+                    equation.test_value,
+                    equation
+                        .numbers
+                        .iter()
+                        .map(u64::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+            })
+            .collect())
+    }
+}
+
+aoc_common::verify_answers!(Day07);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    fn span_of_error(input: &str) -> SourceSpan {
+        parsers::line_error(&parse(input).unwrap_err()).1
+    }
+
+    #[test]
+    fn test_parse_reports_bad_number() {
+        assert_eq!(
+            SourceSpan::from((20, 2)),
+            span_of_error("190: 10 19\n3267: 81 4o 27\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_missing_numbers() {
+        assert_eq!(SourceSpan::from((0, 4)), span_of_error("190: "));
+        assert_eq!(SourceSpan::from((5, 5)), span_of_error("1: 1\n190:5 2"));
+    }
+
+    #[test]
+    fn test_breakdown() -> miette::Result<()> {
+        let input = parse("190: 10 19\n3267: 81 40 27\n83: 17 5\n156: 15 6\n")?;
+        assert_eq!(
+            vec![
+                "10 * 19 = 190 (1 assignment)",
+                "81 * 40 + 27 = 3267 (2 assignments)",
+                "83: 17 5 (no assignment)",
+                "156: 15 6 (no assignment)",
+            ],
+            Day07::breakdown(&input, Part::One, &())?
+        );
+        assert_eq!(
+            "15 || 6 = 156 (1 assignment)",
+            Day07::breakdown(&input, Part::Two, &())?[3]
+        );
+        Ok(())
+    }
+}
@@ -1,108 +1,21 @@
-use itertools::Itertools;
-use nom::{bytes, character, multi, sequence, IResult};
+use aoc_common::solution::Part;
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let result: u64 = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let (_discard, equation) = parse(line).unwrap();
-            let ops = vec![Operator::Add, Operator::Multiply];
-            let num_operators = equation.numbers.len() - 1;
-            tracing::info!(
-                "Line Parse result: {:?}, from input {:?}, num operators: {:?}",
-                equation,
-                line,
-                num_operators
-            );
-
-            let solution = (0..num_operators)
-                .map(|_| ops.clone())
-                .multi_cartesian_product()
-                .find(|operators| {
-                    tracing::debug!("Trying operator permutation: {:?}", operators);
-                    solve(&equation, operators)
-                });
-
-            if solution.is_some() {
-                tracing::info!(
-                    "Solution Found: {:?} -> Adding {:?}",
-                    solution,
-                    equation.test_value
-                );
-                Some(equation.test_value)
-            } else {
-                tracing::warn!("No solutions found for: {:?}", equation,);
-                None
-            }
-        })
-        .sum();
-    tracing::info!("Result: {:?}", result);
-    Ok(result.to_string())
-}
+use crate::solver::{self, total_calibration};
+use crate::{Equation, Operator};
 
-pub(crate) fn parse(input: &str) -> IResult<&str, Equation> {
-    let (_remaining, pair) = sequence::separated_pair(
-        character::complete::u64,
-        bytes::complete::tag(": "),
-        multi::separated_list0(character::complete::space1, character::complete::u64),
-    )(input)?;
-    tracing::trace!("Parse Result: {:?}", pair);
-    Ok((
+#[tracing::instrument(skip(input))]
+pub fn process(input: &[Equation]) -> miette::Result<u64> {
+    Ok(total_calibration(
         input,
-        Equation {
-            test_value: pair.0,
-            numbers: pair.1,
-        },
+        Operator::allowed(Part::One),
+        solver::backward,
     ))
 }
 
-#[tracing::instrument]
-pub(crate) fn solve(equation: &Equation, operators: &Vec<Operator>) -> bool {
-    let mut collector = equation.numbers[0];
-    for (index, number) in equation.numbers[1..].iter().enumerate() {
-        let operator = operators.get(index).unwrap();
-        tracing::trace!("Collector: {:?} -> {:?} {:?}", collector, operator, number);
-        match operator {
-            Operator::Add => collector += number,
-            Operator::Multiply => collector *= number,
-            Operator::Concat => {
-                collector = (collector.to_string() + number.to_string().as_str())
-                    .parse()
-                    .unwrap()
-            }
-        }
-        if collector > equation.test_value {
-            tracing::trace!("Collector Exceeded test value - breaking: {:?}", collector);
-            break;
-        }
-    }
-    tracing::trace!(
-        "Result -> Collector {:?}, operators {:?}, test value: {:?}",
-        collector,
-        operators,
-        equation.test_value
-    );
-    collector == equation.test_value
-}
-
-#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
-pub(crate) struct Equation {
-    pub test_value: u64,
-    pub numbers: Vec<u64>,
-}
-
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) enum Operator {
-    Add,
-    Multiply,
-    Concat,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -118,7 +31,7 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20
 ";
-        assert_eq!("3749", process(input)?);
+        assert_eq!(3749, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,46 +1,25 @@
-use itertools::Itertools;
+use aoc_common::solution::Part;
 
-use crate::part1::{parse, Operator};
+use crate::solver::{self, total_calibration};
+use crate::{Equation, Operator};
 
 #[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let result: u64 = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let (_discard, equation) = parse(line).unwrap();
-            // NOTE: This is the main difference from part1, the extra operator in the list
-            // introduces this as an option for solver attempts
-            let ops = vec![Operator::Add, Operator::Multiply, Operator::Concat];
-
-            let solution = (0..equation.numbers.len() - 1)
-                .map(|_| ops.clone())
-                .multi_cartesian_product()
-                .find(|operators| crate::part1::solve(&equation, operators));
-
-            if solution.is_some() {
-                tracing::info!(
-                    "Solution Found: {:?} -> Adding {:?}",
-                    solution,
-                    equation.test_value
-                );
-                Some(equation.test_value)
-            } else {
-                tracing::warn!("No solutions found for: {:?}", equation,);
-                None
-            }
-        })
-        .sum();
-    tracing::info!("Result: {:?}", result);
-    Ok(result.to_string())
+pub fn process(input: &[Equation]) -> miette::Result<u64> {
+    // NOTE: This is the main difference from part1, concatenation is allowed as well
+    Ok(total_calibration(
+        input,
+        Operator::allowed(Part::Two),
+        solver::backward,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
-    #[test(ignore)]
+    #[test]
     fn test_process() -> miette::Result<()> {
         let input = "
 190: 10 19
@@ -53,14 +32,14 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20
 ";
-        assert_eq!("11387", process(input)?);
+        assert_eq!(11387, process(&parse(input)?)?);
         Ok(())
     }
 
     #[test]
     fn test_process_line3() -> miette::Result<()> {
         let input = "7290: 6 8 6 15";
-        assert_eq!("7290", process(input)?);
+        assert_eq!(7290, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -0,0 +1,353 @@
+use aoc_common::numeric;
+use itertools::Itertools;
+
+use crate::{Equation, Operator};
+
+/// Decides whether an equation can be made true with the given operators.
+pub type Solver = fn(&Equation, &[Operator]) -> bool;
+
+/// Sum of the test values of every equation `solvable` can make true with `operators`.
+pub fn total_calibration(input: &[Equation], operators: &[Operator], solvable: Solver) -> u64 {
+    let result = input
+        .iter()
+        .filter(|equation| {
+            let found = solvable(equation, operators);
+            if found {
+                tracing::info!("Solution Found -> Adding {:?}", equation.test_value);
+            } else {
+                tracing::warn!("No solutions found for: {:?}", equation);
+            }
+            found
+        })
+        .map(|equation| equation.test_value)
+        .sum();
+    tracing::info!("Result: {:?}", result);
+    result
+}
+
+/// What the numbers before `last` have to come to for `operator` applied to them and `last`
+/// to give `target`.
+enum Undo {
+    Exactly(u64),
+    /// Multiplying by zero gives zero whatever came before, even a value too large for a `u64`
+    Anything,
+    Impossible,
+}
+
+/// Invert `operator` when the inverse is exact: the target must be at least the number for `+`,
+/// divisible by it for `*`, and end with its digits for `||`.
+fn undo(operator: Operator, target: u64, last: u64) -> Undo {
+    let undone = match operator {
+        Operator::Add => target.checked_sub(last),
+        Operator::Multiply if last == 0 => {
+            return if target == 0 {
+                Undo::Anything
+            } else {
+                Undo::Impossible
+            }
+        }
+        Operator::Multiply => target.is_multiple_of(last).then(|| target / last),
+        Operator::Concat => numeric::strip_suffix(target, last),
+    };
+    undone.map_or(Undo::Impossible, Undo::Exactly)
+}
+
+/// Work from the test value back towards the first number, undoing the last operator at each
+/// step. Operators are evaluated left to right, so the last number is always the right operand
+/// of the last operator, and only exact inverses are followed, see [`undo`]. Most branches are
+/// cut off after a step or two.
+#[tracing::instrument(level = "debug")]
+pub fn backward(equation: &Equation, operators: &[Operator]) -> bool {
+    fn reaches(target: u64, numbers: &[u64], operators: &[Operator]) -> bool {
+        let Some((&last, rest)) = numbers.split_last() else {
+            return false;
+        };
+        if rest.is_empty() {
+            return target == last;
+        }
+        operators
+            .iter()
+            .any(|operator| match undo(*operator, target, last) {
+                Undo::Exactly(undone) => reaches(undone, rest, operators),
+                Undo::Anything => true,
+                Undo::Impossible => false,
+            })
+    }
+
+    reaches(equation.test_value, &equation.numbers, operators)
+}
+
+/// The first operators found, left to right, that make the equation true, searching like
+/// [`backward`].
+pub fn assignment(equation: &Equation, operators: &[Operator]) -> Option<Vec<Operator>> {
+    fn find(target: u64, numbers: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+        let (&last, rest) = numbers.split_last()?;
+        if rest.is_empty() {
+            return (target == last).then(Vec::new);
+        }
+        operators.iter().find_map(|operator| {
+            let mut before = match undo(*operator, target, last) {
+                Undo::Exactly(undone) => find(undone, rest, operators)?,
+                Undo::Anything => vec![operators[0]; rest.len() - 1],
+                Undo::Impossible => return None,
+            };
+            before.push(*operator);
+            Some(before)
+        })
+    }
+
+    let found = find(equation.test_value, &equation.numbers, operators);
+    if let Some(found) = &found {
+        tracing::debug!("Solution Found: {}", equation.render(found));
+    }
+    found
+}
+
+/// How many different operator assignments make the equation true.
+pub fn count_assignments(equation: &Equation, operators: &[Operator]) -> u64 {
+    fn count(target: u64, numbers: &[u64], operators: &[Operator]) -> u64 {
+        let Some((&last, rest)) = numbers.split_last() else {
+            return 0;
+        };
+        if rest.is_empty() {
+            return u64::from(target == last);
+        }
+        operators
+            .iter()
+            .map(|operator| match undo(*operator, target, last) {
+                Undo::Exactly(undone) => count(undone, rest, operators),
+                Undo::Anything => (operators.len() as u64).pow(rest.len() as u32 - 1),
+                Undo::Impossible => 0,
+            })
+            .sum()
+    }
+
+    count(equation.test_value, &equation.numbers, operators)
+}
+
+/// Try every arrangement of `operators` left to right, which is `operators.len()` to the power
+/// of the number of gaps. Kept to benchmark and check [`backward`] against.
+#[tracing::instrument(level = "debug")]
+pub fn naive(equation: &Equation, operators: &[Operator]) -> bool {
+    arrangements(equation, operators, evaluate)
+}
+
+/// [`naive`] concatenating by formatting both numbers and parsing the result, as it did before
+/// [`numeric::concat`]. Kept to benchmark the digit arithmetic against.
+#[tracing::instrument(level = "debug")]
+pub fn naive_strings(equation: &Equation, operators: &[Operator]) -> bool {
+    arrangements(equation, operators, |equation, arrangement| {
+        apply(equation, arrangement, |a, b| format!("{a}{b}").parse().ok())
+    })
+}
+
+fn arrangements(
+    equation: &Equation,
+    operators: &[Operator],
+    evaluate: fn(&Equation, &[Operator]) -> bool,
+) -> bool {
+    (1..equation.numbers.len())
+        .map(|_| operators.iter().copied())
+        .multi_cartesian_product()
+        .any(|arrangement| {
+            tracing::trace!("Trying operator permutation: {:?}", arrangement);
+            evaluate(equation, &arrangement)
+        })
+}
+
+/// Whether applying `operators` between the numbers left to right gives the test value.
+pub(crate) fn evaluate(equation: &Equation, operators: &[Operator]) -> bool {
+    apply(equation, operators, numeric::concat)
+}
+
+/// [`evaluate`] with `concat` as the concatenation operator.
+fn apply(equation: &Equation, operators: &[Operator], concat: fn(u64, u64) -> Option<u64>) -> bool {
+    // NOTE: `None` once the collector has passed the test value, overflowing included. Every
+    // operator only grows the collector except multiplying by zero, which brings it back to zero
+    // however large it was
+    let mut collector = Some(equation.numbers[0]).filter(|first| *first <= equation.test_value);
+    for (index, (operator, number)) in operators.iter().zip(&equation.numbers[1..]).enumerate() {
+        tracing::trace!("Collector: {:?} -> {:?} {:?}", collector, operator, number);
+        collector = match (operator, collector) {
+            (Operator::Multiply, _) if *number == 0 => Some(0),
+            (_, None) => None,
+            (Operator::Add, Some(collector)) => collector.checked_add(*number),
+            (Operator::Multiply, Some(collector)) => collector.checked_mul(*number),
+            (Operator::Concat, Some(collector)) => concat(collector, *number),
+        }
+        .filter(|next| *next <= equation.test_value);
+        if collector.is_none() && !equation.numbers[index + 2..].contains(&0) {
+            tracing::trace!("Collector Exceeded test value - breaking");
+            return false;
+        }
+    }
+    collector == Some(equation.test_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use aoc_common::bench::Rng;
+    use aoc_common::solution::Part;
+    use test_log::test;
+
+    #[test]
+    fn test_evaluate_treats_overflow_as_too_large() {
+        let equation = Equation {
+            test_value: u64::MAX,
+            numbers: vec![1844674407370955161, 6, 1],
+        };
+        assert!(!evaluate(&equation, &[Operator::Concat, Operator::Add]));
+        assert!(evaluate(
+            &Equation {
+                test_value: u64::MAX,
+                numbers: vec![1844674407370955161, 5],
+            },
+            &[Operator::Concat]
+        ));
+    }
+
+    #[test]
+    fn test_backward_undoes_exactly() -> miette::Result<()> {
+        let equations = parse("190: 10 19\n156: 15 6\n7290: 6 8 6 15\n0: 5 0\n5: 5 0\n")?;
+        let solved: Vec<bool> = equations
+            .iter()
+            .map(|equation| backward(equation, Operator::allowed(Part::Two)))
+            .collect();
+        assert_eq!(vec![true, true, true, true, true], solved);
+        assert!(!backward(&equations[1], Operator::allowed(Part::One)));
+        assert!(!backward(&equations[2], Operator::allowed(Part::One)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_example_assignments() -> miette::Result<()> {
+        use Operator::*;
+        let equations = parse(
+            "
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+",
+        )?;
+        let part1 = Operator::allowed(Part::One);
+        let found: Vec<Option<Vec<Operator>>> = equations
+            .iter()
+            .map(|equation| assignment(equation, part1))
+            .collect();
+        assert_eq!(
+            vec![
+                Some(vec![Multiply]),
+                Some(vec![Multiply, Add]),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(vec![Add, Multiply, Add]),
+            ],
+            found
+        );
+        assert_eq!(
+            vec![1, 2, 0, 0, 0, 0, 0, 0, 1],
+            equations
+                .iter()
+                .map(|equation| count_assignments(equation, part1))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "11 + 6 * 16 + 20 = 292",
+            equations[8].render(&[Add, Multiply, Add])
+        );
+        assert!(evaluate(&equations[1], &[Add, Multiply]));
+
+        // NOTE: The three more the puzzle finds once concatenation is allowed
+        assert_eq!(
+            Some(vec![Concat]),
+            assignment(&equations[3], Operator::allowed(Part::Two))
+        );
+        assert_eq!(
+            Some(vec![Multiply, Concat, Multiply]),
+            assignment(&equations[4], Operator::allowed(Part::Two))
+        );
+        assert_eq!(
+            Some(vec![Concat, Add]),
+            assignment(&equations[6], Operator::allowed(Part::Two))
+        );
+        assert_eq!(
+            "6 * 8 || 6 * 15 = 7290",
+            equations[4].render(&[Multiply, Concat, Multiply])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiplying_by_zero() -> miette::Result<()> {
+        use Operator::*;
+        let equation = &parse("0: 3 4 0")?[0];
+        // NOTE: `3 ? 4 * 0` is zero whatever the first operator is, even though `3 + 4` has
+        // already passed the test value
+        assert_eq!(
+            Some(vec![Add, Multiply]),
+            assignment(equation, Operator::allowed(Part::Two))
+        );
+        assert_eq!(3, count_assignments(equation, Operator::allowed(Part::Two)));
+        assert!(evaluate(equation, &[Add, Multiply]));
+        assert!(naive(equation, Operator::allowed(Part::Two)));
+
+        // NOTE: Zero rescues a prefix too large for a `u64` just the same
+        let equation = &parse("0: 18446744073709551615 2 0")?[0];
+        assert!(evaluate(equation, &[Multiply, Multiply]));
+        assert_eq!(3, count_assignments(equation, Operator::allowed(Part::Two)));
+        assert!(!evaluate(equation, &[Multiply, Add]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_backward_agrees_with_naive() {
+        let mut rng = Rng::new(7);
+        for _ in 0..500 {
+            let numbers: Vec<u64> = (0..rng.range(1..7))
+                .map(|_| if rng.one_in(4) { 0 } else { rng.range(1..20) })
+                .collect();
+            // NOTE: Small test values too, as only those can be reached by multiplying by zero
+            let test_value = if rng.one_in(3) {
+                rng.range(0..20)
+            } else {
+                numbers.iter().sum::<u64>() + rng.range(0..2000)
+            };
+            let equation = Equation {
+                test_value,
+                numbers,
+            };
+            for operators in [Operator::allowed(Part::One), Operator::allowed(Part::Two)] {
+                let solvable = naive(&equation, operators);
+                assert_eq!(solvable, naive_strings(&equation, operators));
+                assert_eq!(
+                    solvable,
+                    backward(&equation, operators),
+                    "{equation:?} with {operators:?}"
+                );
+                let found = assignment(&equation, operators);
+                assert_eq!(solvable, found.is_some());
+                if let Some(found) = found {
+                    assert!(evaluate(&equation, &found), "{}", equation.render(&found));
+                }
+                let expected = (1..equation.numbers.len())
+                    .map(|_| operators.iter().copied())
+                    .multi_cartesian_product()
+                    .filter(|arrangement| evaluate(&equation, arrangement))
+                    .count();
+                assert_eq!(expected as u64, count_assignments(&equation, operators));
+            }
+        }
+    }
+}
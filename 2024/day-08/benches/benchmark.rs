@@ -1,16 +1,32 @@
-use day_08::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_08::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 50,
+    generate,
+});
+
+/// Square map of side `size` with a few antennas per row over 36 frequencies.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let frequencies: Vec<char> = ('0'..='9').chain('a'..='z').collect();
+    let mut input = String::new();
+    for _ in 0..size {
+        input.extend((0..size).map(|_| match rng.one_in(12) {
+            true => *rng.choose(&frequencies),
+            false => '.',
+        }));
+        input.push('\n');
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day08::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day08::solve(divan::black_box(input()), Part::Two).unwrap();
 }
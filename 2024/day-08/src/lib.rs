@@ -1,2 +1,34 @@
+use aoc_common::grid::{Grid, YAxis};
+use aoc_common::solution::Solution;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day08;
+
+pub fn parse(input: &str) -> miette::Result<Grid<char>> {
+    // NOTE: Y axis points up so that X-Y coords make sense
+    let grid = Grid::parse(input, YAxis::Up, |ch| ch)?;
+    tracing::debug!("Detected Bounds: {:?}x{:?}", grid.width(), grid.height());
+    Ok(grid)
+}
+
+impl Solution for Day08 {
+    type Input = Grid<char>;
+    type Answer = usize;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day08);
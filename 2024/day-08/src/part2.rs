@@ -1,30 +1,13 @@
+use aoc_common::grid::Grid;
 use glam::IVec2;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-use crate::part1::in_bounds;
+use crate::part1::antennae_by_frequency;
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut char_map = HashMap::new();
-    for (row, line) in input
-        .lines()
-        .rev() // NOTE: Input is processed row by row in reverse so that X-Y coords make sense
-        .skip_while(|line| line.is_empty())
-        .enumerate()
-    {
-        for antenna in crate::part1::parse_row(row, line) {
-            char_map
-                .entry(antenna.character)
-                .or_insert(Vec::new())
-                .push(antenna.position);
-        }
-    }
-
-    let max_x = input.lines().find(|line| !line.is_empty()).unwrap().len() as i32 - 1;
-    let max_y = input.lines().skip_while(|line| line.is_empty()).count() as i32 - 1;
-    let bounds = IVec2::new(max_x, max_y);
-    tracing::debug!("Detected Bounds: {:?}", &bounds);
+#[tracing::instrument(skip(grid))]
+pub fn process(grid: &Grid<char>) -> miette::Result<usize> {
+    let char_map = antennae_by_frequency(grid);
 
     let antinodes: HashSet<IVec2> = char_map
         .iter()
@@ -32,8 +15,8 @@ pub fn process(input: &str) -> miette::Result<String> {
             antennae.iter().combinations(2).flat_map(move |combo| {
                 tracing::trace!("Antenna combination: {:?} for {:?}", &combo, &character);
                 let mut nodes = vec![*combo[0], *combo[1]];
-                nodes.extend(get_resonant_nodes(combo[0], combo[1], &bounds));
-                nodes.extend(get_resonant_nodes(combo[1], combo[0], &bounds));
+                nodes.extend(get_resonant_nodes(combo[0], combo[1], grid));
+                nodes.extend(get_resonant_nodes(combo[1], combo[0], grid));
                 nodes
             })
         })
@@ -48,9 +31,9 @@ pub fn process(input: &str) -> miette::Result<String> {
             .collect::<Vec<(IVec2, char)>>(),
     );
     let mut output: String = String::new();
-    for row in (0..max_y + 1).rev() {
+    for row in (0..grid.height() as i32).rev() {
         tracing::trace!("Row: {:?}", &row);
-        for col in 0..max_x + 1 {
+        for col in 0..grid.width() as i32 {
             let pos = IVec2::new(col, row);
             if antenna_positions.contains_key(&pos) {
                 output.push(*antenna_positions.get(&pos).unwrap())
@@ -64,15 +47,15 @@ pub fn process(input: &str) -> miette::Result<String> {
     }
     tracing::debug!("Collected Positions: \n{}", output);
 
-    Ok(antinodes.len().to_string())
+    Ok(antinodes.len())
 }
 
-fn get_resonant_nodes(lhs: &IVec2, rhs: &IVec2, bounds: &IVec2) -> Vec<IVec2> {
+fn get_resonant_nodes(lhs: &IVec2, rhs: &IVec2, grid: &Grid<char>) -> Vec<IVec2> {
     let diff = rhs - lhs;
     tracing::trace!("Antenna diff: {:?}", &diff);
     let mut candidate = rhs + diff;
     let mut antinodes = Vec::new();
-    while in_bounds(&candidate, bounds) {
+    while grid.in_bounds(candidate) {
         tracing::trace!("Antinode found: {:?}", &candidate);
         antinodes.push(candidate);
         candidate += diff;
@@ -83,6 +66,7 @@ fn get_resonant_nodes(lhs: &IVec2, rhs: &IVec2, bounds: &IVec2) -> Vec<IVec2> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -101,7 +85,7 @@ mod tests {
 ............
 ............
 ";
-        assert_eq!("34", process(input)?);
+        assert_eq!(34, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,16 +1,30 @@
-use day_09::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_09::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 19999,
+    generate,
+});
+
+/// Disk map of `size` digits, alternating file and free space lengths.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input: String = (0..size)
+        .map(|index| match index % 2 {
+            0 => char::from(b'0' + rng.range(1..10) as u8),
+            _ => char::from(b'0' + rng.range(0..10) as u8),
+        })
+        .collect();
+    input.push('\n');
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day09::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day09::solve(divan::black_box(input()), Part::Two).unwrap();
 }
@@ -1,2 +1,54 @@
+use aoc_common::input::Input;
+use aoc_common::parsers;
+use aoc_common::solution::Solution;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day09;
+
+/// Parse the dense disk map into alternating file and free space sizes
+pub fn parse(input: &str) -> miette::Result<Vec<u32>> {
+    let input = Input::new(input);
+    let mut sizes = vec![];
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        sizes.extend(parsers::digits(&input, line, "a disk map of digits")?);
+    }
+    Ok(sizes)
+}
+
+impl Solution for Day09 {
+    type Input = Vec<u32>;
+    type Answer = u64;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day09);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_reports_bad_size() {
+        let error = parse("\n2333133121x14\n").unwrap_err();
+        assert_eq!(
+            ('x', SourceSpan::from((11, 1))),
+            parsers::cell_error(&error)
+        );
+    }
+}
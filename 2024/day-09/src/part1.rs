@@ -1,23 +1,13 @@
-use itertools::Itertools;
-use miette::{miette, Result};
+use miette::Result;
 
 #[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> Result<String> {
-    let input_line = input.chars().filter(|ch| *ch != '\n');
+pub fn process(input: &[u32]) -> Result<u64> {
     let mut files: Vec<Option<u32>> = vec![];
 
-    for (file_index, mut chunk) in (&input_line.chunks(2)).into_iter().enumerate() {
-        let file_size: u32 = chunk
-            .next()
-            .ok_or(miette!("Invalid Input chunk"))?
-            .to_digit(10)
-            .ok_or(miette!("Invalid file size"))?;
-
-        let free_space: u32 = chunk
-            .next()
-            .unwrap_or('0') // Last file in a sequence may or may not have free space after
-            .to_digit(10)
-            .ok_or(miette!("Invalid free space"))?;
+    for (file_index, chunk) in input.chunks(2).enumerate() {
+        let file_size = chunk[0];
+        // Last file in a sequence may or may not have free space after
+        let free_space = chunk.get(1).copied().unwrap_or(0);
 
         tracing::trace!(
             "File index: {file_index}, File size: {file_size}, Free space: {free_space}"
@@ -79,18 +69,19 @@ pub fn process(input: &str) -> Result<String> {
         .enumerate()
         .map(|(index, file_index)| index as u64 * *file_index as u64)
         .sum();
-    Ok(checksum.to_string())
+    Ok(checksum)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
     fn test_process() -> miette::Result<()> {
         let input = "2333133121414131402";
-        assert_eq!("1928", process(input)?);
+        assert_eq!(1928, process(&parse(input)?)?);
         Ok(())
     }
 }
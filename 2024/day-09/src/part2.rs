@@ -1,169 +1,100 @@
-use itertools::Itertools;
-use miette::miette;
+use aoc_common::extent::ExtentSet;
 
 #[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let input_line = input.chars().filter(|ch| *ch != '\n');
-    let mut files: Vec<File> = vec![];
-    let mut file_position: usize = 0;
+pub fn process(input: &[u32]) -> miette::Result<u64> {
+    // NOTE: Sizes are single digits, so no file needs a gap of more than 9 blocks
+    let mut disk: ExtentSet<u64> = ExtentSet::with_longest_query(9);
+    let mut files = vec![];
+    let mut position = 0;
 
-    for (file_id, mut chunk) in (&input_line.chunks(2)).into_iter().enumerate() {
-        let file_size = chunk
-            .next()
-            .ok_or(miette!("Invalid Input chunk"))?
-            .to_digit(10)
-            .ok_or(miette!("Invalid file size"))?;
-
-        let free_space = chunk
-            .next()
-            .unwrap_or('0') // Last file in a sequence may or may not have free space after
-            .to_digit(10)
-            .ok_or(miette!("Invalid free space"))?;
+    for (file_id, chunk) in input.chunks(2).enumerate() {
+        let file_size = chunk[0] as usize;
+        // Last file in a sequence may or may not have free space after
+        let free_space = chunk.get(1).copied().unwrap_or(0) as usize;
 
         tracing::trace!("File index: {file_id}, File size: {file_size}, Free space: {free_space}");
-        files.push(File {
-            id: Some(file_id as u64),
-            size: file_size as u64,
-            position: file_position,
-        });
-        file_position += file_size as usize;
+        let file = position..position + file_size;
+        disk.occupy(file.clone(), file_id as u64);
+        files.push(file);
+        position += file_size;
 
-        files.push(File {
-            id: None,
-            size: free_space as u64,
-            position: file_position,
-        });
-        file_position += free_space as usize;
+        disk.free(position..position + free_space);
+        position += free_space;
     }
-    print_file_map(&files);
-
-    // TODO: The below loops over files and tries to keep track as we go, but what we should
-    // actually be doing is just picking up the file indexes first and repeatedly re-processing the
-    // same list of objects for movement as it would make it a lot easier to keep track of things
-    // than the nested iterators to find gaps and movement candidates
-
-    let file_ids: Vec<u64> = files.clone().iter().filter_map(|f| f.id).rev().collect();
-
-    for file_id in file_ids {
-        files = defrag_move_file(file_id, files);
+    tracing::trace!("Disk Map:\t{}", disk_map(&disk));
+
+    // NOTE: Each file moves at most once, in descending id order, so its extent is still where
+    // it started when its turn comes
+    for file in files.into_iter().rev().filter(|file| !file.is_empty()) {
+        let Some(gap) = disk.first_free(file.len(), file.start) else {
+            tracing::debug!("No gap found for file at {:?}", file);
+            continue;
+        };
+        let (_, file_id) = disk.release(file.start).expect("file is on the disk");
+        tracing::trace!("Moving file {file_id} from {file:?} to {}", gap.start);
+        disk.occupy(gap.start..gap.start + file.len(), file_id);
     }
-    print_file_map(&files);
+    tracing::trace!("Disk Map:\t{}", disk_map(&disk));
 
-    let checksum: u64 = files
-        .iter()
-        .map(|file| match file.id {
-            Some(id) => (file.position..file.position + file.size as usize)
-                .map(|pos| pos as u64 * id)
-                .sum(),
-            None => 0,
-        })
+    let checksum: u64 = disk
+        .occupied()
+        .map(|(extent, id)| extent.map(|pos| pos as u64 * id).sum::<u64>())
         .sum();
 
-    Ok(checksum.to_string())
+    Ok(checksum)
 }
 
-fn defrag_move_file(file_id: u64, files: Vec<File>) -> Vec<File> {
-    // New copies of the file and gap for update
-    let mut file = files
-        .iter()
-        .find(|f| f.id == Some(file_id))
-        .expect("Missing file id")
-        .clone();
-    let gap = files
-        .iter()
-        .find(|f| f.id.is_none() && f.size >= file.size && f.position < file.position);
-
-    let mut gap = match gap {
-        Some(gap) => gap.clone(),
-        None => {
-            tracing::debug!("No gap found for file {:?}", file);
-            return files.clone();
-        }
-    };
-
-    // Moving file to a new location will leave a gap
-    let new_file_gap = File {
-        id: None,
-        size: file.size,
-        position: file.position,
-    };
-
-    // Update the file to new position
-    let previous_position = file.position;
-    file.position = gap.position;
-    tracing::trace!(
-        "Updated file {:?} position from {:?} to {:?}",
-        file_id,
-        previous_position,
-        file.position
-    );
-
-    // Drop gap size and move as needed
-    if gap.size == file.size {
-        gap.size = 0;
-        tracing::trace!("Dropped Gap at position {:?}", gap.position);
-    } else {
-        gap.size -= file.size;
-        gap.position = file.position + file.size as usize;
-        tracing::trace!(
-            "Moved Gap to position {:?}, size to {:?}",
-            gap.position,
-            gap.size
-        );
+/// One glyph per block, files drawn as their id in base 36 wrapping past `z`, free space as `.`
+fn disk_map(disk: &ExtentSet<u64>) -> String {
+    let mut map = String::new();
+    let mut block = 0;
+    for (extent, id) in disk.occupied() {
+        let glyph = char::from_digit((id % 36) as u32, 36).expect("below the radix");
+        map.extend(std::iter::repeat_n('.', extent.start - block));
+        map.extend(std::iter::repeat_n(glyph, extent.len()));
+        block = extent.end;
     }
-
-    let mut result: Vec<File> = files
-        .iter()
-        .filter_map(|existing_file| {
-            if existing_file.position == file.position || existing_file.id == file.id {
-                None
-            } else {
-                Some(existing_file.clone())
-            }
-        })
-        .collect();
-
-    result.push(file);
-    result.push(new_file_gap);
-    if gap.size > 0 {
-        result.push(gap);
-    }
-
-    result.into_iter().sorted_by_key(|f| f.position).collect()
-}
-
-fn print_file_map(files: &[File]) -> String {
-    let disk_map: String = files
-        .iter()
-        .map(|file| {
-            let char = match file.id {
-                Some(index) => index.to_string(),
-                None => ".".to_string(),
-            };
-            char.repeat(file.size as usize)
-        })
-        .collect();
-
-    tracing::trace!("Disk Map:\t{}", disk_map);
-    disk_map
-}
-
-#[derive(Eq, PartialEq, Clone, Debug)]
-struct File {
-    id: Option<u64>, // None here means a Gap
-    size: u64,
-    position: usize,
+    map
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
     fn test_process() -> miette::Result<()> {
         let input = "2333133121414131402";
-        assert_eq!("2858", process(input)?);
+        assert_eq!(2858, process(&parse(input)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disk_map() -> miette::Result<()> {
+        let mut disk = ExtentSet::new();
+        disk.occupy(0..2, 0);
+        disk.occupy(2..4, 9);
+        disk.free(4..5);
+        disk.occupy(5..8, 1);
+        assert_eq!("0099.111", disk_map(&disk));
+        Ok(())
+    }
+
+    #[test]
+    fn test_disk_map_with_long_ids() -> miette::Result<()> {
+        let mut disk = ExtentSet::new();
+        disk.occupy(0..1, 9);
+        disk.free(1..2);
+        disk.occupy(2..4, 10);
+        disk.occupy(4..5, 36);
+        assert_eq!("9.aa0", disk_map(&disk));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_with_long_ids() -> miette::Result<()> {
+        assert_eq!(436, process(&parse("1212121212121212121212121")?)?);
         Ok(())
     }
 }
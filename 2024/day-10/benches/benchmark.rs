@@ -1,16 +1,34 @@
-use day_10::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_10::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 50,
+    generate,
+});
+
+/// Square topographic map of side `size` with heights rising and falling in gentle slopes.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            let height = match rng.one_in(8) {
+                true => rng.range(0..10) as usize,
+                false => (x + y) % 10,
+            };
+            input += &height.to_string();
+        }
+        input.push('\n');
+    }
+    input
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day10::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day10::solve(divan::black_box(input()), Part::Two).unwrap();
 }
@@ -1,2 +1,76 @@
+use aoc_common::grid::{Grid, YAxis};
+use aoc_common::parsers;
+use aoc_common::solution::Solution;
+use glam::IVec2;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day10;
+
+/// Parse the topographic map, `.` marks impassable tiles with no height
+pub fn parse(input: &str) -> miette::Result<Grid<Option<u32>>> {
+    Ok(parsers::grid(
+        input,
+        YAxis::Up,
+        "a topographic map of heights 0 to 9 and `.`",
+        |ch| match ch {
+            '.' => Some(None),
+            _ => ch.to_digit(10).map(Some),
+        },
+    )?)
+}
+
+/// Positions of every trailhead, the tiles of height 0.
+pub(crate) fn trailheads(points: &Grid<Option<u32>>) -> impl Iterator<Item = IVec2> + '_ {
+    points.positions(|height| *height == Some(0))
+}
+
+/// Neighbouring positions exactly one step higher, the only moves a hiking trail allows.
+pub(crate) fn uphill(points: &Grid<Option<u32>>, position: IVec2) -> Vec<IVec2> {
+    let Some(Some(height)) = points.get(position) else {
+        return vec![];
+    };
+    points
+        .neighbours4(position)
+        .filter(|(_, next)| **next == Some(height + 1))
+        .map(|(next, _)| next)
+        .collect()
+}
+
+pub(crate) fn is_summit(points: &Grid<Option<u32>>, position: IVec2) -> bool {
+    points.get(position) == Some(&Some(9))
+}
+
+impl Solution for Day10 {
+    type Input = Grid<Option<u32>>;
+    type Answer = u32;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!(Day10);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_reports_bad_height() {
+        let error = parse("0123\n1.x4\n").unwrap_err();
+        assert_eq!(('x', SourceSpan::from((7, 1))), parsers::cell_error(&error));
+    }
+}
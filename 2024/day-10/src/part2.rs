@@ -1,74 +1,30 @@
-use glam::IVec2;
-use std::collections::{HashMap, VecDeque};
+use aoc_common::grid::Grid;
+use aoc_common::search;
 
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+use crate::{is_summit, trailheads, uphill};
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let points: HashMap<IVec2, u32> = input
-        .lines()
-        .rev()
-        .enumerate()
-        .flat_map(move |(row_index, row)| {
-            row.chars().enumerate().filter_map(move |(col_index, col)| {
-                let height = match col {
-                    '.' => return None,
-                    height => height,
-                };
-                Some((
-                    IVec2::new(col_index as i32, row_index as i32),
-                    height.to_string().parse().unwrap(),
-                ))
-            })
+/// Sum of trailhead ratings, the number of distinct hiking trails from each trailhead.
+#[tracing::instrument(skip(points))]
+pub fn process(points: &Grid<Option<u32>>) -> miette::Result<u32> {
+    let rating = trailheads(points)
+        .map(|trailhead| {
+            let trails = search::count_paths(
+                trailhead,
+                |position| uphill(points, *position),
+                |position| is_summit(points, *position),
+            );
+            tracing::debug!("Trailhead {:?} has {} trails", trailhead, trails);
+            trails as u32
         })
-        .collect();
+        .sum();
 
-    let trailheads: Vec<&IVec2> = points
-        .iter()
-        .filter_map(
-            |(point, height)| {
-                if *height == 0 {
-                    Some(point)
-                } else {
-                    None
-                }
-            },
-        )
-        .collect();
-
-    let mut score: u32 = 0;
-    for start_position in trailheads.iter() {
-        tracing::debug!("Starting trace of trail: {:?}", start_position);
-        let mut queue = VecDeque::<IVec2>::new();
-        queue.push_back(**start_position);
-
-        while let Some(position) = queue.pop_front() {
-            let height = points.get(&position).unwrap();
-
-            // Explore all possible directions and add paths
-            for dir in DIRECTIONS.iter() {
-                let next_position = position + dir;
-                let next_height = match points.get(&next_position) {
-                    Some(height) => height,
-                    None => continue,
-                };
-                if *height == 8 && *next_height == 9 {
-                    tracing::trace!("Found Trail end: {:?} -> {:?}", position, next_position);
-                    score += 1;
-                } else if *next_height == height + 1 {
-                    tracing::trace!("Tracking path: {:?} -> {:?}", position, next_position);
-                    queue.push_front(next_position);
-                }
-            }
-        }
-    }
-
-    Ok(score.to_string())
+    Ok(rating)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
@@ -82,7 +38,7 @@ mod tests {
 ..8765.
 ..9....
 ";
-        assert_eq!("3", process(input)?);
+        assert_eq!(3, process(&parse(input)?)?);
         Ok(())
     }
 
@@ -96,7 +52,7 @@ mod tests {
 4.6789
 56789.
 ";
-        assert_eq!("227", process(input)?);
+        assert_eq!(227, process(&parse(input)?)?);
         Ok(())
     }
 
@@ -112,7 +68,7 @@ mod tests {
 01329801
 10456732
 ";
-        assert_eq!("81", process(input)?);
+        assert_eq!(81, process(&parse(input)?)?);
         Ok(())
     }
 }
@@ -1,16 +1,47 @@
-use day_11::*;
+use aoc_common::bench::{Generator, Rng};
+use aoc_common::solution::{Part, Solution};
+use day_2024_11::*;
 
-fn main() {
-    // Run registered benchmarks.
-    divan::main();
+aoc_common::bench_main!(Generator {
+    default_size: 8,
+    generate,
+});
+
+/// `size` stones engraved with numbers of up to seven digits.
+fn generate(size: usize, rng: &mut Rng) -> String {
+    let stones: Vec<String> = (0..size)
+        .map(|_| rng.range(0..10_000_000).to_string())
+        .collect();
+    stones.join(" ") + "\n"
 }
 
 #[divan::bench]
 fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day11::solve(divan::black_box(input()), Part::One).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt"))).unwrap();
+    Day11::solve(divan::black_box(input()), Part::Two).unwrap();
+}
+
+/// Splitting engravings with digit arithmetic against the string round trip it replaced, both
+/// over the 75 blinks of part 2.
+mod blink_stone {
+    use super::*;
+    use day_2024_11::part2::count_stones;
+
+    #[divan::bench]
+    fn strings(bencher: divan::Bencher) {
+        let stones = Day11::parse(input()).unwrap();
+        bencher.bench(|| count_stones(divan::black_box(&stones), 75, blink_stone_strings).unwrap());
+    }
+
+    #[divan::bench]
+    fn digits(bencher: divan::Bencher) {
+        let stones = Day11::parse(input()).unwrap();
+        bencher.bench(|| {
+            count_stones(divan::black_box(&stones), 75, day_2024_11::blink_stone).unwrap()
+        });
+    }
 }
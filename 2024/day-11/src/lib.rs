@@ -1,2 +1,121 @@
+use aoc_common::input::Input;
+use aoc_common::numeric;
+use aoc_common::parsers;
+use aoc_common::solution::Solution;
+use miette::Diagnostic;
+use thiserror::Error;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day11;
+
+/// Number of times to blink in each part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Blinks {
+    pub part1: usize,
+    pub part2: usize,
+}
+
+impl Default for Blinks {
+    fn default() -> Self {
+        Blinks {
+            part1: 25,
+            part2: 75,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> miette::Result<Vec<u64>> {
+    let input = Input::new(input);
+    let lines = parsers::lines(
+        &input,
+        input.lines(),
+        "stones engraved with numbers",
+        parsers::integers,
+    )?;
+    Ok(lines.into_iter().flatten().collect())
+}
+
+#[derive(Error, Diagnostic, Debug, PartialEq, Eq)]
+#[error("Stone {stone} is too large to multiply by 2024")]
+#[diagnostic(help("engraved numbers must stay below {}", u64::MAX))]
+pub struct OverflowError {
+    pub stone: u64,
+}
+
+/// Turns one stone into the one or two stones that replace it when blinking.
+pub type Blink = fn(u64) -> Result<(u64, Option<u64>), OverflowError>;
+
+/// The one or two stones that replace `stone` when blinking.
+pub fn blink_stone(stone: u64) -> Result<(u64, Option<u64>), OverflowError> {
+    if stone == 0 {
+        return Ok((1, None));
+    }
+    if let Some((left, right)) = numeric::split_halves(stone) {
+        return Ok((left, Some(right)));
+    }
+    let engraved = stone.checked_mul(2024).ok_or(OverflowError { stone })?;
+    Ok((engraved, None))
+}
+
+/// [`blink_stone`] splitting the engraving by formatting it and parsing both halves, as it did
+/// before [`numeric::split_halves`]. Kept to benchmark the digit arithmetic against.
+pub fn blink_stone_strings(stone: u64) -> Result<(u64, Option<u64>), OverflowError> {
+    let engraving = stone.to_string();
+    if stone == 0 || !engraving.len().is_multiple_of(2) {
+        return blink_stone(stone);
+    }
+    let (left, right) = engraving.split_at(engraving.len() / 2);
+    let half = |digits: &str| digits.parse().expect("halves of a u64 are u64s");
+    Ok((half(left), Some(half(right))))
+}
+
+impl Solution for Day11 {
+    type Input = Vec<u64>;
+    type Answer = usize;
+    type Config = Blinks;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input, config.part1)
+    }
+
+    fn part2(input: &Self::Input, config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input, config.part2)
+    }
+}
+
+aoc_common::verify_answers!(Day11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::SourceSpan;
+    use test_log::test;
+
+    #[test]
+    fn test_parse_reports_bad_stone() {
+        let error = parse("125 17 -3\n").unwrap_err();
+        assert_eq!(SourceSpan::from((7, 2)), parsers::line_error(&error).1);
+    }
+
+    #[test]
+    fn test_blink_stone() {
+        assert_eq!(Ok((1, None)), blink_stone(0));
+        assert_eq!(Ok((2024, None)), blink_stone(1));
+        assert_eq!(Ok((10, Some(0))), blink_stone(1000));
+        assert_eq!(
+            Err(OverflowError {
+                stone: 10_000_000_000_000_000
+            }),
+            blink_stone(10_000_000_000_000_000)
+        );
+        for stone in [0, 1, 17, 1000, 253000, 2097446912, u64::MAX] {
+            assert_eq!(blink_stone(stone), blink_stone_strings(stone));
+        }
+    }
+}
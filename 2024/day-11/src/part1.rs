@@ -1,76 +1,62 @@
+use crate::blink_stone;
+
 #[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut stones: Vec<String> = input.split_whitespace().map(|s| s.to_string()).collect();
+pub fn process(input: &[u64], blinks: usize) -> miette::Result<usize> {
+    let mut stones = input.to_vec();
 
-    for _ in 0..25 {
-        stones = blink(stones);
+    for _ in 0..blinks {
+        stones = blink(&stones)?;
     }
 
-    Ok(stones.len().to_string())
+    Ok(stones.len())
 }
 
-fn blink(stones: Vec<String>) -> Vec<String> {
-    stones.iter().fold(Vec::new(), |mut acc, stone| {
-        let stone = stone.as_str();
-        if stone == "0" {
-            acc.push("1".to_string());
-        } else if stone.len() % 2 == 0 {
-            let (left, right) = stone.split_at(stone.len() / 2);
-            acc.push(left.parse::<u64>().unwrap().to_string());
-            acc.push(right.parse::<u64>().unwrap().to_string());
-        } else {
-            acc.push((&stone.parse::<u64>().unwrap() * 2024).to_string());
-        }
-        acc
-    })
+fn blink(stones: &[u64]) -> miette::Result<Vec<u64>> {
+    let mut new_stones = Vec::with_capacity(stones.len() * 2);
+    for stone in stones {
+        let (left, right) = blink_stone(*stone)?;
+        new_stones.push(left);
+        new_stones.extend(right);
+    }
+    Ok(new_stones)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        assert_eq!("55312".to_string(), process("125 17").unwrap());
+        assert_eq!(55312, process(&parse("125 17")?, 25)?);
         Ok(())
     }
 
     #[test]
     fn test_one_blink() -> miette::Result<()> {
-        let input = "0 1 10 99 999"
-            .split(" ")
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
         assert_eq!(
-            "1 2024 1 0 9 9 2021976".split(" ").collect::<Vec<_>>(),
-            blink(input)
+            parse("1 2024 1 0 9 9 2021976")?,
+            blink(&parse("0 1 10 99 999")?)?
         );
         Ok(())
     }
 
     #[test]
     fn test_six_blink_example() -> miette::Result<()> {
-        let input = "125 17"
-            .split(" ")
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
-
-        let first = blink(input);
-        assert_eq!("253000 1 7".split(" ").collect::<Vec<_>>(), first);
+        let first = blink(&parse("125 17")?)?;
+        assert_eq!(parse("253000 1 7")?, first);
 
-        let second = blink(first);
-        assert_eq!("253 0 2024 14168".split(" ").collect::<Vec<_>>(), second);
+        let second = blink(&first)?;
+        assert_eq!(parse("253 0 2024 14168")?, second);
 
         let mut last = second.clone();
         for _ in 0..4 {
             // Blink called twice, we want to get to 6
-            last = blink(last);
+            last = blink(&last)?;
         }
         assert_eq!(
-            "2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2"
-                .split(" ")
-                .collect::<Vec<_>>(),
+            parse("2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2")?,
             last
         );
         assert!(last.len() == 22);
@@ -1,52 +1,56 @@
 use std::collections::HashMap;
 
+use crate::{blink_stone, Blink};
+
 #[tracing::instrument(skip(input))]
-pub fn process(input: &str, count: usize) -> miette::Result<String> {
-    let mut stones: HashMap<u64, usize> = input
-        .split_whitespace()
-        .map(|stone| (stone.parse().unwrap(), 1))
-        .collect();
-
-    for _ in 0..count {
-        stones = blink(stones);
+pub fn process(input: &[u64], blinks: usize) -> miette::Result<usize> {
+    count_stones(input, blinks, blink_stone)
+}
+
+/// Number of stones after blinking `blinks` times, replacing each stone with `blink`. Stones
+/// with the same number are counted together.
+pub fn count_stones(input: &[u64], blinks: usize, blink: Blink) -> miette::Result<usize> {
+    let mut stones: HashMap<u64, usize> = HashMap::new();
+    for stone in input {
+        *stones.entry(*stone).or_insert(0) += 1;
     }
 
-    Ok(stones.values().sum::<usize>().to_string())
+    for _ in 0..blinks {
+        stones = blink_all(stones, blink)?;
+    }
+
+    Ok(stones.values().sum::<usize>())
 }
 
-fn blink(stones: HashMap<u64, usize>) -> HashMap<u64, usize> {
+fn blink_all(stones: HashMap<u64, usize>, blink: Blink) -> miette::Result<HashMap<u64, usize>> {
     tracing::info!("blinking: {stones:?}");
-    let mut new_stones: HashMap<u64, usize> = HashMap::new();
-
-    for (stone, count) in stones.iter() {
-        let stone_name = stone.to_string();
-        if stone_name == "0" {
-            *new_stones.entry(1).or_insert(0) += count;
-        } else if stone_name.len() % 2 == 0 {
-            let (left, right) = stone_name.split_at(stone_name.len() / 2);
-            *new_stones.entry(left.parse::<u64>().unwrap()).or_insert(0) += count;
-            *new_stones.entry(right.parse::<u64>().unwrap()).or_insert(0) += count;
-        } else {
-            *new_stones.entry(stone * 2024).or_insert(0) += count;
+    let mut new_stones: HashMap<u64, usize> = HashMap::with_capacity(stones.len() * 2);
+
+    for (stone, count) in stones {
+        let (left, right) = blink(stone)?;
+        *new_stones.entry(left).or_insert(0) += count;
+        if let Some(right) = right {
+            *new_stones.entry(right).or_insert(0) += count;
         }
     }
-    new_stones
+    Ok(new_stones)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse;
     use test_log::test;
 
     #[test]
     fn test_process_6() -> miette::Result<()> {
-        assert_eq!("22".to_string(), process("125 17", 6).unwrap());
+        assert_eq!(22, process(&parse("125 17")?, 6)?);
         Ok(())
     }
 
     #[test]
     fn test_process_25() -> miette::Result<()> {
-        assert_eq!("55312".to_string(), process("125 17", 25).unwrap());
+        assert_eq!(55312, process(&parse("125 17")?, 25)?);
         Ok(())
     }
 }
@@ -0,0 +1,82 @@
+//! Digit arithmetic against the format-and-parse round trips it replaced in days 7 and 11.
+
+use aoc_common::bench::Rng;
+use aoc_common::numeric;
+use divan::black_box;
+
+fn main() {
+    divan::main();
+}
+
+/// Numbers of one to nine digits, like day 7's collectors and day 11's stones.
+fn numbers() -> Vec<u64> {
+    let mut rng = Rng::new(7);
+    (0..1000)
+        .map(|_| {
+            let digits = rng.range(1..10) as u32;
+            rng.range(0..10u64.pow(digits))
+        })
+        .collect()
+}
+
+mod concat {
+    use super::*;
+
+    #[divan::bench]
+    fn string(bencher: divan::Bencher) {
+        let numbers = numbers();
+        bencher.bench(|| {
+            numbers
+                .windows(2)
+                .map(|pair| {
+                    (pair[0].to_string() + pair[1].to_string().as_str())
+                        .parse::<u64>()
+                        .unwrap()
+                })
+                .fold(0u64, u64::wrapping_add)
+        });
+    }
+
+    #[divan::bench]
+    fn digits(bencher: divan::Bencher) {
+        let numbers = numbers();
+        bencher.bench(|| {
+            numbers
+                .windows(2)
+                .map(|pair| numeric::concat(black_box(pair[0]), pair[1]).unwrap())
+                .fold(0u64, u64::wrapping_add)
+        });
+    }
+}
+
+mod split_halves {
+    use super::*;
+
+    #[divan::bench]
+    fn string(bencher: divan::Bencher) {
+        let numbers = numbers();
+        bencher.bench(|| {
+            numbers
+                .iter()
+                .filter_map(|number| {
+                    let name = black_box(number).to_string();
+                    (name.len() % 2 == 0).then(|| {
+                        let (left, right) = name.split_at(name.len() / 2);
+                        (left.parse::<u64>().unwrap(), right.parse::<u64>().unwrap())
+                    })
+                })
+                .fold(0u64, |sum, (left, right)| sum.wrapping_add(left ^ right))
+        });
+    }
+
+    #[divan::bench]
+    fn digits(bencher: divan::Bencher) {
+        let numbers = numbers();
+        bencher.bench(|| {
+            numbers
+                .iter()
+                .filter_map(|number| numeric::split_halves(*black_box(number)))
+                .fold(0u64, |sum, (left, right)| sum.wrapping_add(left ^ right))
+        });
+    }
+}
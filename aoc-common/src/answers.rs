@@ -0,0 +1,427 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::{Diagnostic, IntoDiagnostic, WrapErr};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::solution::{Answer, Part, Solution, Solved};
+
+/// File name of the answer store, kept next to a day's `input.txt`.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AnswerStoreError {
+    #[error("Failed to read answers from {path}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to write answers to {path}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Answers file {path} is not valid")]
+    #[diagnostic(help("expected tables like [part1] with an `answer = \"...\"` key"))]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("Failed to serialize answers")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// Accepted answers for one day's real input, stored as `answers.toml`:
+///
+/// ```toml
+/// [part1]
+/// answer = "41"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnswerStore {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part1: Option<PartRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part2: Option<PartRecord>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    /// Lowest answer the site has reported as too high.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    too_high: Option<i64>,
+    /// Highest answer the site has reported as too low.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    too_low: Option<i64>,
+    /// Other rejected answers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    wrong: Vec<String>,
+}
+
+/// Outcome of comparing a computed answer against the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+/// How one part of a day compares with its recorded answer, see [`verify_parts`].
+#[derive(Debug)]
+pub enum Verdict {
+    Pass(Answer),
+    Fail {
+        answer: Answer,
+        expected: String,
+    },
+    /// The part has a recorded answer but failed to solve.
+    Error(miette::Report),
+    /// Nothing recorded to compare with, so whatever the part gives now can't be a regression.
+    Unrecorded(miette::Result<Answer>),
+}
+
+/// Why an answer can be rejected without asking the site.
+#[derive(Error, Diagnostic, Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    #[error("Part already has the accepted answer {0}")]
+    AlreadyAccepted(String),
+    #[error("Answer was already rejected")]
+    KnownWrong,
+    #[error("Answer must be lower than {0}, which was too high")]
+    TooHigh(i64),
+    #[error("Answer must be higher than {0}, which was too low")]
+    TooLow(i64),
+}
+
+impl AnswerStore {
+    /// Path of the answer store that belongs to the given input file.
+    pub fn beside(input: &Path) -> PathBuf {
+        input.with_file_name(ANSWERS_FILE)
+    }
+
+    /// Load a store, treating a missing file as one with no answers yet.
+    pub fn load(path: &Path) -> Result<Self, AnswerStoreError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(AnswerStoreError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        toml::from_str(&text).map_err(|source| AnswerStoreError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswerStoreError> {
+        let text = toml::to_string(self)?;
+        fs::write(path, text).map_err(|source| AnswerStoreError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        self.record(part)?.answer.as_deref()
+    }
+
+    pub fn set_answer(&mut self, part: Part, answer: &Answer) {
+        self.record_mut(part).answer = Some(answer.to_string());
+    }
+
+    pub fn check(&self, part: Part, answer: &Answer) -> Check {
+        match self.answer(part) {
+            None => Check::Missing,
+            Some(expected) if expected == answer.to_string() => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    /// Check an answer against everything the site has already told us, before submitting it.
+    pub fn reject(&self, part: Part, answer: &Answer) -> Option<Rejection> {
+        let record = self.record(part)?;
+        let text = answer.to_string();
+        if let Some(accepted) = &record.answer {
+            return Some(Rejection::AlreadyAccepted(accepted.clone()));
+        }
+        if record.wrong.contains(&text) {
+            return Some(Rejection::KnownWrong);
+        }
+        let value = answer.as_integer()?;
+        match (record.too_high, record.too_low) {
+            (Some(high), _) if value >= high.into() => Some(Rejection::TooHigh(high)),
+            (_, Some(low)) if value <= low.into() => Some(Rejection::TooLow(low)),
+            _ => None,
+        }
+    }
+
+    pub fn record_too_high(&mut self, part: Part, answer: &Answer) {
+        match bound(answer) {
+            Some(value) => {
+                let record = self.record_mut(part);
+                record.too_high = Some(record.too_high.map_or(value, |high| high.min(value)));
+            }
+            None => self.record_wrong(part, answer),
+        }
+    }
+
+    pub fn record_too_low(&mut self, part: Part, answer: &Answer) {
+        match bound(answer) {
+            Some(value) => {
+                let record = self.record_mut(part);
+                record.too_low = Some(record.too_low.map_or(value, |low| low.max(value)));
+            }
+            None => self.record_wrong(part, answer),
+        }
+    }
+
+    pub fn record_wrong(&mut self, part: Part, answer: &Answer) {
+        let text = answer.to_string();
+        let record = self.record_mut(part);
+        if !record.wrong.contains(&text) {
+            record.wrong.push(text);
+        }
+    }
+
+    fn record(&self, part: Part) -> Option<&PartRecord> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    fn record_mut(&mut self, part: Part) -> &mut PartRecord {
+        match part {
+            Part::One => self.part1.get_or_insert_with(PartRecord::default),
+            Part::Two => self.part2.get_or_insert_with(PartRecord::default),
+        }
+    }
+}
+
+/// Integer answers usable as a too high/low bound. TOML integers are 64-bit, so anything larger
+/// is only remembered as a wrong answer.
+fn bound(answer: &Answer) -> Option<i64> {
+    answer
+        .as_integer()
+        .and_then(|value| i64::try_from(value).ok())
+}
+
+/// Solve both parts with `solve_parts` against the input at `input_path` and compare them
+/// with the answer store beside it. `None` when the input hasn't been downloaded, but an input
+/// that can't be read or parsed is an error.
+pub fn verify_parts(
+    input_path: &Path,
+    solve_parts: impl FnOnce(&str, &[Part]) -> miette::Result<Vec<Solved>>,
+) -> miette::Result<Option<Vec<(Part, Verdict)>>> {
+    let input = match fs::read_to_string(input_path) {
+        Ok(input) => input,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", input_path.display()))
+        }
+    };
+    let store = AnswerStore::load(&AnswerStore::beside(input_path))?;
+
+    let verdicts = solve_parts(&input, &Part::BOTH)?
+        .into_iter()
+        .map(|Solved { part, answer, .. }| {
+            let verdict = match answer {
+                answer if store.answer(part).is_none() => Verdict::Unrecorded(answer),
+                Err(e) => Verdict::Error(e),
+                Ok(answer) => match store.check(part, &answer) {
+                    Check::Fail { expected } => Verdict::Fail { answer, expected },
+                    Check::Pass | Check::Missing => Verdict::Pass(answer),
+                },
+            };
+            (part, verdict)
+        })
+        .collect();
+    Ok(Some(verdicts))
+}
+
+/// Solve both parts of the day in `dir` against its `input.txt` and compare them with its
+/// answer store. Passes without checking anything when the input hasn't been downloaded, but an
+/// input that can't be read is an error.
+pub fn verify_day<S: Solution>(dir: &Path) -> miette::Result<()> {
+    let input_path = dir.join("input.txt");
+    let Some(verdicts) = verify_parts(&input_path, |input, parts| {
+        S::solve_parts(input, parts, false)
+    })?
+    else {
+        println!("skipping, no input at {}", input_path.display());
+        return Ok(());
+    };
+
+    for (part, verdict) in verdicts {
+        match verdict {
+            Verdict::Pass(_) => {}
+            Verdict::Fail { answer, expected } => {
+                miette::bail!("part {part} answered {answer}, expected {expected}")
+            }
+            Verdict::Error(e) => return Err(e.wrap_err(format!("part {part}"))),
+            Verdict::Unrecorded(_) => println!("skipping part {part}, no recorded answer"),
+        }
+    }
+    Ok(())
+}
+
+/// Generate a `#[test]` that checks a day's solution against its recorded answers for the real
+/// input. The test passes trivially when `input.txt` is absent, such as in CI.
+#[macro_export]
+macro_rules! verify_answers {
+    ($solution:ty) => {
+        #[cfg(test)]
+        #[test]
+        fn verify_answers() -> miette::Result<()> {
+            $crate::answers::verify_day::<$solution>(::std::path::Path::new(env!(
+                "CARGO_MANIFEST_DIR"
+            )))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let mut store = AnswerStore::default();
+        store.set_answer(Part::One, &Answer::from(41u32));
+        let text = toml::to_string(&store).map_err(AnswerStoreError::from)?;
+        assert_eq!("[part1]\nanswer = \"41\"\n", text);
+        assert_eq!(
+            store,
+            toml::from_str(&text).map_err(|e| miette::miette!("{e}"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() {
+        let mut store = AnswerStore::default();
+        store.set_answer(Part::Two, &Answer::from("ABC"));
+        assert_eq!(Check::Missing, store.check(Part::One, &Answer::from(1u8)));
+        assert_eq!(Check::Pass, store.check(Part::Two, &Answer::from("ABC")));
+        assert_eq!(
+            Check::Fail {
+                expected: "ABC".to_string()
+            },
+            store.check(Part::Two, &Answer::from("ABD"))
+        );
+    }
+
+    #[test]
+    fn test_reject_uses_recorded_feedback() {
+        let mut store = AnswerStore::default();
+        assert_eq!(None, store.reject(Part::One, &Answer::from(100u32)));
+
+        store.record_too_high(Part::One, &Answer::from(100u32));
+        store.record_too_high(Part::One, &Answer::from(120u32));
+        store.record_too_low(Part::One, &Answer::from(10u32));
+        store.record_wrong(Part::One, &Answer::from(50u32));
+
+        assert_eq!(
+            Some(Rejection::TooHigh(100)),
+            store.reject(Part::One, &Answer::from(120u32))
+        );
+        assert_eq!(
+            Some(Rejection::TooLow(10)),
+            store.reject(Part::One, &Answer::from(3u32))
+        );
+        assert_eq!(
+            Some(Rejection::KnownWrong),
+            store.reject(Part::One, &Answer::from(50u32))
+        );
+        assert_eq!(None, store.reject(Part::One, &Answer::from(51u32)));
+        assert_eq!(None, store.reject(Part::Two, &Answer::from(120u32)));
+
+        store.set_answer(Part::One, &Answer::from(51u32));
+        assert_eq!(
+            Some(Rejection::AlreadyAccepted("51".to_string())),
+            store.reject(Part::One, &Answer::from(52u32))
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() -> miette::Result<()> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("no-such-answers.toml");
+        assert_eq!(AnswerStore::default(), AnswerStore::load(&path)?);
+        Ok(())
+    }
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = usize;
+        type Answer = usize;
+        type Config = ();
+
+        fn parse(input: &str) -> miette::Result<Self::Input> {
+            Ok(input.lines().count())
+        }
+
+        fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+            Ok(*input)
+        }
+
+        fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+            Ok(*input)
+        }
+    }
+
+    #[test]
+    fn test_verify_parts() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-common-{}-verdicts", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create scratch dir");
+        let input_path = dir.join("input.txt");
+        fs::write(&input_path, "a\nb\n").expect("write input");
+        fs::write(dir.join(ANSWERS_FILE), "[part1]\nanswer = \"3\"\n").expect("write answers");
+
+        let verdicts = verify_parts(&input_path, |input, parts| {
+            Lines::solve_parts(input, parts, false)
+        })?
+        .expect("input exists");
+
+        assert!(matches!(
+            &verdicts[0],
+            (Part::One, Verdict::Fail { answer, expected })
+                if *answer == Answer::Integer(2) && expected == "3"
+        ));
+        assert!(matches!(
+            &verdicts[1],
+            (Part::Two, Verdict::Unrecorded(Ok(Answer::Integer(2))))
+        ));
+        assert!(verify_day::<Lines>(&dir).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_day_only_skips_missing_input() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-common-{}-verify", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create scratch dir");
+        verify_day::<Lines>(&dir)?;
+
+        fs::write(dir.join("input.txt"), b"1\n\xff\n").expect("write input");
+        let error = verify_day::<Lines>(&dir).unwrap_err();
+        assert!(error.to_string().starts_with("read "), "{error:?}");
+        Ok(())
+    }
+}
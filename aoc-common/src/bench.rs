@@ -0,0 +1,196 @@
+use std::path::Path;
+
+/// Selects the bench input: `auto` (the default), `real`, `example` or `generated`.
+pub const SOURCE_ENV: &str = "AOC_BENCH_INPUT";
+/// Overrides the size passed to a day's input generator.
+pub const SIZE_ENV: &str = "AOC_BENCH_SIZE";
+
+/// Where a bench's input came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The private `input.txt`.
+    Real,
+    /// The committed `example.txt` from the puzzle description.
+    Example,
+    /// Produced by the day's generator.
+    Generated,
+}
+
+/// Builds a random but valid puzzle input. What `size` counts, lines or grid width, is up to
+/// the day, and `default_size` should be close to the real input.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub default_size: usize,
+    pub generate: fn(usize, &mut Rng) -> String,
+}
+
+/// Pick the bench input for the day crate in `dir`, as [`select`] does with the source and size
+/// from [`SOURCE_ENV`] and [`SIZE_ENV`].
+pub fn load(dir: &Path, generator: Option<Generator>) -> Option<String> {
+    select(
+        dir,
+        generator,
+        std::env::var(SOURCE_ENV).ok().as_deref(),
+        std::env::var(SIZE_ENV).ok().as_deref(),
+    )
+}
+
+/// Pick the bench input for the day crate in `dir`. By default this tries `input.txt`, then
+/// `example.txt`, then the generator. Returns `None`, after saying why, when nothing is available
+/// so the bench can exit without running.
+pub fn select(
+    dir: &Path,
+    generator: Option<Generator>,
+    source: Option<&str>,
+    size: Option<&str>,
+) -> Option<String> {
+    let sources = match source {
+        None | Some("auto") => vec![Source::Real, Source::Example, Source::Generated],
+        Some("real") => vec![Source::Real],
+        Some("example") => vec![Source::Example],
+        Some("generated") => vec![Source::Generated],
+        Some(other) => {
+            eprintln!("Unknown {SOURCE_ENV}={other:?}, expected auto, real, example or generated");
+            return None;
+        }
+    };
+
+    for &source in &sources {
+        let input = match source {
+            Source::Real => std::fs::read_to_string(dir.join("input.txt")).ok(),
+            Source::Example => std::fs::read_to_string(dir.join("example.txt"))
+                .ok()
+                .filter(|example| !example.trim().is_empty()),
+            Source::Generated => generator.map(|generator| {
+                let size = size
+                    .and_then(|size| size.parse().ok())
+                    .unwrap_or(generator.default_size);
+                eprintln!("Generating bench input of size {size}");
+                (generator.generate)(size, &mut Rng::new(size as u64))
+            }),
+        };
+        if let Some(input) = input {
+            if source != Source::Real {
+                eprintln!("Benchmarking {} against {source:?} input", dir.display());
+            }
+            return Some(input);
+        }
+    }
+    eprintln!(
+        "Skipping benches for {}: no input available from {:?}",
+        dir.display(),
+        sources
+    );
+    None
+}
+
+/// Small xorshift generator so bench inputs are reproducible without extra dependencies.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // NOTE: xorshift gets stuck on zero, so mix the seed into a non-zero state
+        Rng(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform-ish value in `range`, which must not be empty.
+    pub fn range(&mut self, range: std::ops::Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// True with probability `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Generate the `main` for a divan bench along with an `input()` function returning the input
+/// chosen by [`load`]. Pass a [`Generator`] to allow generated inputs.
+#[macro_export]
+macro_rules! bench_main {
+    () => {
+        $crate::bench_main!(@main None);
+    };
+    ($generator:expr) => {
+        $crate::bench_main!(@main Some($generator));
+    };
+    (@main $generator:expr) => {
+        static INPUT: ::std::sync::OnceLock<String> = ::std::sync::OnceLock::new();
+
+        fn input() -> &'static str {
+            INPUT.get().expect("bench input is loaded before divan runs")
+        }
+
+        fn main() {
+            let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+            if let Some(input) = $crate::bench::load(dir, $generator) {
+                INPUT.set(input).expect("bench input is only loaded once");
+                // Run registered benchmarks.
+                divan::main();
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_rng_is_reproducible() {
+        let mut first = Rng::new(7);
+        let mut second = Rng::new(7);
+        for _ in 0..100 {
+            let value = first.range(10..20);
+            assert_eq!(value, second.range(10..20));
+            assert!((10..20).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut items: Vec<u32> = (0..50).collect();
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!((0..50).collect::<Vec<_>>(), items);
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn test_select_falls_back_to_generator() {
+        let generator = Generator {
+            default_size: 3,
+            generate: |size, _| "x".repeat(size),
+        };
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("no-such-day");
+        assert_eq!(
+            Some("xxx".to_string()),
+            select(&dir, Some(generator), None, None)
+        );
+        assert_eq!(None, select(&dir, None, None, None));
+        assert_eq!(
+            Some("xxxxx".to_string()),
+            select(&dir, Some(generator), Some("generated"), Some("5"))
+        );
+        assert_eq!(None, select(&dir, Some(generator), Some("example"), None));
+        assert_eq!(None, select(&dir, Some(generator), Some("other"), None));
+    }
+}
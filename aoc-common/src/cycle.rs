@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How a deterministic simulation ends, where `step` gives the state after a state, or `None`
+/// once the simulation stops.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome<S> {
+    /// `step` returned `None` after `steps` transitions, leaving `last` as the final state.
+    Terminated { steps: usize, last: S },
+    /// States repeat forever. The state after `start` steps is the first one on the cycle, and
+    /// it comes round again every `length` steps.
+    Cycle { start: usize, length: usize },
+}
+
+impl<S> Outcome<S> {
+    pub fn is_cycle(&self) -> bool {
+        matches!(self, Outcome::Cycle { .. })
+    }
+}
+
+/// Detect a cycle with Brent's algorithm, which only keeps two states in memory and compares
+/// them for equality. Prefer this when states are small to step and cheap to compare.
+pub fn brent<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Outcome<S> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut steps = 0;
+    let Some(mut hare) = step(&initial) else {
+        return Outcome::Terminated {
+            steps,
+            last: initial,
+        };
+    };
+    steps += 1;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = match step(&hare) {
+            Some(next) => next,
+            None => return Outcome::Terminated { steps, last: hare },
+        };
+        steps += 1;
+        length += 1;
+    }
+
+    Outcome::Cycle {
+        start: cycle_start(initial, length, step),
+        length,
+    }
+}
+
+/// Detect a cycle with Floyd's tortoise and hare, also in constant memory. Brent's variant
+/// usually needs fewer steps, this is kept for comparison.
+pub fn floyd<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Outcome<S> {
+    let mut steps = 0;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    loop {
+        for _ in 0..2 {
+            hare = match step(&hare) {
+                Some(next) => next,
+                None => return Outcome::Terminated { steps, last: hare },
+            };
+            steps += 1;
+        }
+        tortoise = step(&tortoise).expect("tortoise follows the hare's path");
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    let mut length = 1;
+    let mut runner = step(&tortoise).expect("the cycle never terminates");
+    while runner != tortoise {
+        runner = step(&runner).expect("the cycle never terminates");
+        length += 1;
+    }
+
+    Outcome::Cycle {
+        start: cycle_start(initial, length, step),
+        length,
+    }
+}
+
+/// Detect a cycle by remembering every state seen. This steps each state exactly once, at the
+/// cost of memory, so suits states that are expensive to step.
+pub fn hashed<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Outcome<S> {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut state = initial;
+    for steps in 1.. {
+        state = match step(&state) {
+            Some(next) => next,
+            None => {
+                return Outcome::Terminated {
+                    steps: steps - 1,
+                    last: state,
+                }
+            }
+        };
+        if let Some(start) = seen.insert(state.clone(), steps) {
+            return Outcome::Cycle {
+                start,
+                length: steps - start,
+            };
+        }
+    }
+    unreachable!("the step counter never runs out")
+}
+
+/// The state after `n` steps, skipping whole cycles instead of simulating them, so `n` can be
+/// far beyond what could be stepped through. Returns `None` if the simulation terminates first.
+pub fn fast_forward<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    n: usize,
+) -> Option<S> {
+    let remaining = match brent(initial.clone(), &mut step) {
+        Outcome::Terminated { steps, .. } if steps < n => return None,
+        Outcome::Terminated { .. } => n,
+        Outcome::Cycle { start, .. } if n <= start => n,
+        Outcome::Cycle { start, length } => start + (n - start) % length,
+    };
+    advance(initial, remaining, &mut step)
+}
+
+/// Step `n` times from `state`.
+fn advance<S>(mut state: S, n: usize, step: &mut impl FnMut(&S) -> Option<S>) -> Option<S> {
+    for _ in 0..n {
+        state = step(&state)?;
+    }
+    Some(state)
+}
+
+/// Index of the first state on a cycle of known `length`, found by walking two states `length`
+/// apart until they meet.
+fn cycle_start<S: Clone + PartialEq>(
+    initial: S,
+    length: usize,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> usize {
+    let mut ahead =
+        advance(initial.clone(), length, &mut step).expect("the cycle never terminates");
+    let mut behind = initial;
+    let mut start = 0;
+    while behind != ahead {
+        behind = step(&behind).expect("the cycle never terminates");
+        ahead = step(&ahead).expect("the cycle never terminates");
+        start += 1;
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    /// 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 7 -> 3, so the cycle starts after 3 steps and has
+    /// 5 states.
+    fn lollipop(state: &u32) -> Option<u32> {
+        Some(if *state == 7 { 3 } else { state + 1 })
+    }
+
+    fn stops_at_five(state: &u32) -> Option<u32> {
+        (*state < 5).then_some(state + 1)
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        let cycle = Outcome::Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(cycle, brent(0, lollipop));
+        assert_eq!(cycle, floyd(0, lollipop));
+        assert_eq!(cycle, hashed(0, lollipop));
+
+        let terminated = Outcome::Terminated { steps: 5, last: 5 };
+        assert_eq!(terminated, brent(0, stops_at_five));
+        assert_eq!(terminated, floyd(0, stops_at_five));
+        assert_eq!(terminated, hashed(0, stops_at_five));
+    }
+
+    #[test]
+    fn test_cycle_from_the_start() {
+        let cycle = Outcome::Cycle {
+            start: 0,
+            length: 4,
+        };
+        let step = |state: &u32| Some((state + 1) % 4);
+        assert_eq!(cycle, brent(0, step));
+        assert_eq!(cycle, floyd(0, step));
+        assert_eq!(cycle, hashed(0, step));
+        assert!(brent(0, |state: &u32| Some(*state)).is_cycle());
+    }
+
+    #[test]
+    fn test_immediate_termination() {
+        let outcome = Outcome::Terminated { steps: 0, last: 9 };
+        assert_eq!(outcome, brent(9, |_: &u32| None));
+        assert_eq!(outcome, floyd(9, |_: &u32| None));
+        assert_eq!(outcome, hashed(9, |_: &u32| None));
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        assert_eq!(Some(2), fast_forward(0, lollipop, 2));
+        assert_eq!(Some(3), fast_forward(0, lollipop, 8));
+        assert_eq!(Some(5), fast_forward(0, lollipop, 1_000_000_000));
+        assert_eq!(Some(5), fast_forward(0, stops_at_five, 5));
+        assert_eq!(None, fast_forward(0, stops_at_five, 6));
+    }
+}
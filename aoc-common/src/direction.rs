@@ -0,0 +1,222 @@
+use std::str::FromStr;
+
+use glam::{IVec2, UVec2};
+use miette::Diagnostic;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::grid::YAxis;
+
+/// Compass heading on a grid, covering both the cardinal and diagonal directions.
+///
+/// Variants are declared clockwise starting from `North`, which is "up" when the grid is drawn.
+#[derive(
+    Default, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum Direction {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+#[derive(Error, Diagnostic, Debug, PartialEq, Eq)]
+#[error("Unknown direction {0:?}")]
+#[diagnostic(help("expected one of ^ > v <, or a compass point such as N, NE or SW"))]
+pub struct ParseDirectionError(pub String);
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    /// The four cardinal directions, clockwise from North.
+    pub fn cardinal() -> impl Iterator<Item = Direction> {
+        Self::CARDINAL.into_iter()
+    }
+
+    /// All eight directions, clockwise from North.
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    /// Rotate 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Rotate 90 degrees anti-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Unit step for this direction on a grid whose Y axis points the given way.
+    pub fn offset(self, y_axis: YAxis) -> IVec2 {
+        let offset = IVec2::from(self);
+        match y_axis {
+            YAxis::Up => offset,
+            YAxis::Down => IVec2::new(offset.x, -offset.y),
+        }
+    }
+
+    /// Step an unsigned position one unit in this direction with the Y axis pointing up,
+    /// returning `None` if that would leave the positive quadrant.
+    pub fn step_uvec2(self, position: UVec2) -> Option<UVec2> {
+        let next = position.as_ivec2() + IVec2::from(self);
+        next.cmpge(IVec2::ZERO).all().then(|| next.as_uvec2())
+    }
+
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % Self::ALL.len()]
+    }
+}
+
+/// Unit step with the Y axis pointing up, so `North` is `IVec2::Y`.
+impl From<Direction> for IVec2 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => IVec2::Y,
+            Direction::NorthEast => IVec2::ONE,
+            Direction::East => IVec2::X,
+            Direction::SouthEast => IVec2::new(1, -1),
+            Direction::South => IVec2::NEG_Y,
+            Direction::SouthWest => IVec2::NEG_ONE,
+            Direction::West => IVec2::NEG_X,
+            Direction::NorthWest => IVec2::new(-1, 1),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '^' | 'N' => Ok(Direction::North),
+            '>' | 'E' => Ok(Direction::East),
+            'v' | 'S' => Ok(Direction::South),
+            '<' | 'W' => Ok(Direction::West),
+            other => Err(ParseDirectionError(other.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction::NorthEast),
+            "SE" => Ok(Direction::SouthEast),
+            "SW" => Ok(Direction::SouthWest),
+            "NW" => Ok(Direction::NorthWest),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => Direction::try_from(ch),
+                    _ => Err(ParseDirectionError(s.to_string())),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_turn_right_cycles_cardinals() {
+        let mut direction = Direction::North;
+        let mut seen = vec![];
+        for _ in 0..4 {
+            seen.push(direction);
+            direction = direction.turn_right();
+        }
+        assert_eq!(Direction::CARDINAL.to_vec(), seen);
+        assert_eq!(Direction::North, direction);
+    }
+
+    #[test]
+    fn test_turn_left_and_reverse() {
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::NorthWest, Direction::SouthWest.turn_right());
+        assert_eq!(Direction::SouthEast, Direction::NorthEast.turn_right());
+        assert_eq!(Direction::SouthWest, Direction::NorthEast.reverse());
+        for direction in Direction::all() {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.reverse(), direction.turn_right().turn_right());
+        }
+    }
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(IVec2::new(0, 1), Direction::North.offset(YAxis::Up));
+        assert_eq!(IVec2::new(0, -1), Direction::North.offset(YAxis::Down));
+        assert_eq!(IVec2::new(1, 1), Direction::SouthEast.offset(YAxis::Down));
+        assert_eq!(
+            IVec2::ZERO,
+            Direction::all().map(IVec2::from).sum::<IVec2>()
+        );
+    }
+
+    #[test]
+    fn test_step_uvec2() {
+        assert_eq!(
+            Some(UVec2::new(1, 1)),
+            Direction::North.step_uvec2(UVec2::new(1, 0))
+        );
+        assert_eq!(None, Direction::South.step_uvec2(UVec2::new(1, 0)));
+        assert_eq!(None, Direction::NorthWest.step_uvec2(UVec2::new(0, 3)));
+    }
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        for (glyph, expected) in "^>v<".chars().zip(Direction::cardinal()) {
+            assert_eq!(expected, Direction::try_from(glyph)?);
+        }
+        for (name, expected) in ["N", "NE", "E", "SE", "S", "SW", "W", "NW"]
+            .into_iter()
+            .zip(Direction::all())
+        {
+            assert_eq!(expected, name.parse::<Direction>()?);
+        }
+        assert_eq!(
+            Err(ParseDirectionError("x".to_string())),
+            Direction::try_from('x')
+        );
+        assert!("NNE".parse::<Direction>().is_err());
+        Ok(())
+    }
+}
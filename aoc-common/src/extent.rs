@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+/// Disjoint half-open extents of a line, such as blocks on a disk, each either occupied by a
+/// value or free.
+///
+/// Occupying part of a free extent splits it, and releasing an occupied extent coalesces it
+/// with the free space either side. Free extents are also indexed by length, so finding the
+/// leftmost one that fits takes a lookup per length from the one asked for up to the longest
+/// query, see [`ExtentSet::with_longest_query`].
+#[derive(Clone, Debug)]
+pub struct ExtentSet<T> {
+    occupied: BTreeMap<usize, (usize, T)>,
+    free: BTreeMap<usize, usize>,
+    /// Starts of free extents by length, with every extent of `longest_query` or more together
+    free_by_len: BTreeMap<usize, BTreeSet<usize>>,
+    longest_query: usize,
+}
+
+impl<T> Default for ExtentSet<T> {
+    fn default() -> Self {
+        Self::with_longest_query(usize::MAX)
+    }
+}
+
+impl<T> ExtentSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty set where [`ExtentSet::first_free`] is usually asked for at most `longest`
+    /// positions, such as day 9's files of at most 9 blocks. Those queries look at no more than
+    /// `longest` lengths however many different lengths coalescing creates, longer ones scan the
+    /// free extents of `longest` or more from the left.
+    pub fn with_longest_query(longest: usize) -> Self {
+        ExtentSet {
+            occupied: BTreeMap::new(),
+            free: BTreeMap::new(),
+            free_by_len: BTreeMap::new(),
+            longest_query: longest.max(1),
+        }
+    }
+
+    /// Mark `range` as free, merging it with any free extents it touches. Empty ranges are
+    /// ignored. `range` must not overlap an occupied extent.
+    pub fn free(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        debug_assert!(
+            self.occupied_overlapping(&range).is_none(),
+            "{range:?} is occupied"
+        );
+        let mut merged = range;
+        while let Some(extent) = self.free_touching(&merged) {
+            self.remove_free(extent.start);
+            merged = merged.start.min(extent.start)..merged.end.max(extent.end);
+        }
+        self.insert_free(merged);
+    }
+
+    /// Occupy `range` with `value`, splitting any free extents it covers. Empty ranges are
+    /// ignored. `range` must not overlap another occupied extent.
+    pub fn occupy(&mut self, range: Range<usize>, value: T) {
+        if range.is_empty() {
+            return;
+        }
+        debug_assert!(
+            self.occupied_overlapping(&range).is_none(),
+            "{range:?} is already occupied"
+        );
+        while let Some(extent) = self.free_overlapping(&range) {
+            self.remove_free(extent.start);
+            self.insert_free(extent.start..range.start.max(extent.start));
+            self.insert_free(range.end.min(extent.end)..extent.end);
+        }
+        self.occupied.insert(range.start, (range.end, value));
+    }
+
+    /// Free the occupied extent starting at `start`, returning where it was and its value.
+    pub fn release(&mut self, start: usize) -> Option<(Range<usize>, T)> {
+        let (end, value) = self.occupied.remove(&start)?;
+        self.free(start..end);
+        Some((start..end, value))
+    }
+
+    /// The occupied extent covering `position`, if any.
+    pub fn get(&self, position: usize) -> Option<(Range<usize>, &T)> {
+        self.occupied
+            .range(..=position)
+            .next_back()
+            .filter(|(_, (end, _))| position < *end)
+            .map(|(start, (end, value))| (*start..*end, value))
+    }
+
+    /// The leftmost free extent with room for `len` positions ending no later than `before`.
+    pub fn first_free(&self, len: usize, before: usize) -> Option<Range<usize>> {
+        let len = len.max(1);
+        let start = if len <= self.longest_query {
+            self.free_by_len
+                .range(len..)
+                .filter_map(|(_, starts)| starts.first())
+                .min()?
+        } else {
+            self.free_by_len
+                .get(&self.longest_query)?
+                .iter()
+                .find(|start| self.free[start] - **start >= len)?
+        };
+        (start + len <= before).then(|| *start..self.free[start])
+    }
+
+    /// Occupied extents and their values, from left to right.
+    pub fn occupied(&self) -> impl Iterator<Item = (Range<usize>, &T)> {
+        self.occupied
+            .iter()
+            .map(|(start, (end, value))| (*start..*end, value))
+    }
+
+    /// Free extents from left to right, never touching each other.
+    pub fn free_extents(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.free.iter().map(|(start, end)| *start..*end)
+    }
+
+    fn insert_free(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        self.free_by_len
+            .entry(range.len().min(self.longest_query))
+            .or_default()
+            .insert(range.start);
+        self.free.insert(range.start, range.end);
+    }
+
+    fn remove_free(&mut self, start: usize) {
+        let end = self.free.remove(&start).expect("free extent exists");
+        let len = (end - start).min(self.longest_query);
+        let starts = self
+            .free_by_len
+            .get_mut(&len)
+            .expect("free extent is indexed by length");
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.free_by_len.remove(&len);
+        }
+    }
+
+    /// A free extent overlapping or directly next to `range`.
+    fn free_touching(&self, range: &Range<usize>) -> Option<Range<usize>> {
+        self.free
+            .range(..=range.end)
+            .next_back()
+            .filter(|(_, end)| **end >= range.start)
+            .map(|(start, end)| *start..*end)
+    }
+
+    fn free_overlapping(&self, range: &Range<usize>) -> Option<Range<usize>> {
+        self.free
+            .range(..range.end)
+            .next_back()
+            .filter(|(_, end)| **end > range.start)
+            .map(|(start, end)| *start..*end)
+    }
+
+    fn occupied_overlapping(&self, range: &Range<usize>) -> Option<Range<usize>> {
+        self.occupied
+            .range(..range.end)
+            .next_back()
+            .filter(|(_, (end, _))| *end > range.start)
+            .map(|(start, (end, _))| *start..*end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    /// `00...111...2`, the start of the day 9 example.
+    fn disk() -> ExtentSet<char> {
+        let mut disk = ExtentSet::new();
+        disk.occupy(0..2, '0');
+        disk.free(2..5);
+        disk.occupy(5..8, '1');
+        disk.free(8..11);
+        disk.occupy(11..12, '2');
+        disk
+    }
+
+    #[test]
+    fn test_occupy_splits_free_space() {
+        let mut disk = disk();
+        disk.occupy(3..4, 'x');
+        assert_eq!(
+            vec![2..3, 4..5, 8..11],
+            disk.free_extents().collect::<Vec<_>>()
+        );
+        assert_eq!(Some((3..4, &'x')), disk.get(3));
+        assert_eq!(Some((5..8, &'1')), disk.get(7));
+        assert_eq!(None, disk.get(8));
+    }
+
+    #[test]
+    fn test_release_coalesces_free_space() {
+        let mut disk = disk();
+        assert_eq!(Some((5..8, '1')), disk.release(5));
+        assert_eq!(None, disk.release(5));
+        assert_eq!(vec![2..11], disk.free_extents().collect::<Vec<_>>());
+        assert_eq!(
+            vec![(0..2, &'0'), (11..12, &'2')],
+            disk.occupied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_first_free() {
+        let mut disk = disk();
+        disk.occupy(2..4, 'x');
+        // NOTE: Free space is now 4..5 and 8..11
+        assert_eq!(Some(4..5), disk.first_free(1, 12));
+        assert_eq!(Some(8..11), disk.first_free(2, 12));
+        assert_eq!(Some(8..11), disk.first_free(3, 11));
+        assert_eq!(None, disk.first_free(3, 10));
+        assert_eq!(None, disk.first_free(4, 12));
+        assert_eq!(None, disk.first_free(1, 4));
+    }
+
+    #[test]
+    fn test_first_free_past_longest_query() {
+        let mut disk = ExtentSet::with_longest_query(2);
+        disk.free(0..30);
+        for (start, len) in [(0, 1), (3, 4), (10, 2), (20, 5)] {
+            disk.occupy(start..start + len, 'x');
+        }
+        // NOTE: Free space is now 1..3, 7..10, 12..20 and 25..30, the last three indexed together
+        assert_eq!(Some(1..3), disk.first_free(2, 30));
+        assert_eq!(Some(7..10), disk.first_free(3, 30));
+        assert_eq!(Some(12..20), disk.first_free(4, 30));
+        assert_eq!(Some(12..20), disk.first_free(8, 30));
+        assert_eq!(None, disk.first_free(9, 30));
+        disk.release(20);
+        assert_eq!(Some(12..30), disk.first_free(9, 30));
+    }
+}
@@ -0,0 +1,319 @@
+use glam::IVec2;
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::direction::Direction;
+use crate::input::{Input, Line};
+
+/// Which way the Y axis points once the input has been parsed.
+///
+/// `Down` keeps reading order, so the first line of the input is `y = 0`. `Up` flips the rows so
+/// that the last line is `y = 0` and X-Y coords behave like a regular cartesian plane.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    #[default]
+    Down,
+    Up,
+}
+
+#[derive(Error, Diagnostic, Debug, PartialEq, Eq)]
+pub enum GridError {
+    #[error("Grid input contains no rows")]
+    Empty,
+    #[error("Grid row {row} has {found} columns, expected {expected}")]
+    #[diagnostic(help("every row of a grid must have the same width"))]
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Dense rectangular grid of cells, addressed by `IVec2` positions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    y_axis: YAxis,
+    // NOTE: Cells are stored in reading order (top row first) regardless of the Y axis direction
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid from text, mapping every character to a cell. The text is normalised by
+    /// [`Input`], so blank lines surrounding the grid and line endings are ignored.
+    pub fn parse(
+        input: &str,
+        y_axis: YAxis,
+        mut cell: impl FnMut(char) -> T,
+    ) -> Result<Self, GridError> {
+        Self::try_parse(input, y_axis, |ch, _span| Ok(cell(ch)))
+    }
+
+    /// Like [`Grid::parse`], but cells may be rejected. `cell` is given the span of the
+    /// character in the original input so the error can point at it.
+    #[tracing::instrument(skip(input, cell))]
+    pub fn try_parse<E: From<GridError>>(
+        input: &str,
+        y_axis: YAxis,
+        mut cell: impl FnMut(char, SourceSpan) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let rows: Vec<Line> = Input::new(input).lines().collect();
+
+        let width = rows.first().ok_or(GridError::Empty)?.chars().count();
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for (row, line) in rows.iter().enumerate() {
+            let before = cells.len();
+            for (index, ch) in line.char_indices() {
+                cells.push(cell(ch, (line.offset + index, ch.len_utf8()).into())?);
+            }
+            let found = cells.len() - before;
+            if found != width {
+                return Err(GridError::Ragged {
+                    row,
+                    expected: width,
+                    found,
+                }
+                .into());
+            }
+        }
+        tracing::trace!("Parsed grid {}x{}", width, rows.len());
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            y_axis,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn y_axis(&self) -> YAxis {
+        self.y_axis
+    }
+
+    pub fn in_bounds(&self, position: IVec2) -> bool {
+        position.x >= 0
+            && position.y >= 0
+            && (position.x as usize) < self.width
+            && (position.y as usize) < self.height
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<&T> {
+        self.index(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: IVec2) -> Option<&mut T> {
+        self.index(position).map(|index| &mut self.cells[index])
+    }
+
+    /// All cells with their positions, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.position(index), cell))
+    }
+
+    /// Position one step away in the given direction, honouring the grid's Y axis. The result
+    /// may be out of bounds.
+    pub fn step(&self, position: IVec2, direction: Direction) -> IVec2 {
+        position + direction.offset(self.y_axis)
+    }
+
+    /// In-bounds orthogonal neighbours of a position, clockwise from North.
+    pub fn neighbours4(&self, position: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbours(position, &Direction::CARDINAL)
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours of a position, clockwise from North.
+    pub fn neighbours8(&self, position: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbours(position, &Direction::ALL)
+    }
+
+    /// Position of the first cell in reading order matching the predicate.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.position(index))
+    }
+
+    /// Positions of every cell matching the predicate, in reading order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        self.iter()
+            .filter_map(move |(position, cell)| predicate(cell).then_some(position))
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        position: IVec2,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (IVec2, &'a T)> {
+        directions.iter().filter_map(move |direction| {
+            let neighbour = self.step(position, *direction);
+            self.get(neighbour).map(|cell| (neighbour, cell))
+        })
+    }
+
+    fn index(&self, position: IVec2) -> Option<usize> {
+        if !self.in_bounds(position) {
+            return None;
+        }
+        let (x, y) = (position.x as usize, position.y as usize);
+        let row = match self.y_axis {
+            YAxis::Down => y,
+            YAxis::Up => self.height - 1 - y,
+        };
+        Some(row * self.width + x)
+    }
+
+    fn position(&self, index: usize) -> IVec2 {
+        let (row, x) = (index / self.width, index % self.width);
+        let y = match self.y_axis {
+            YAxis::Down => row,
+            YAxis::Up => self.height - 1 - row,
+        };
+        IVec2::new(x as i32, y as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const INPUT: &str = "
+abc
+def
+";
+
+    #[test]
+    fn test_parse_y_down() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, YAxis::Down, |ch| ch)?;
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&'a'), grid.get(IVec2::new(0, 0)));
+        assert_eq!(Some(&'f'), grid.get(IVec2::new(2, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_y_up() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, YAxis::Up, |ch| ch)?;
+        assert_eq!(Some(&'d'), grid.get(IVec2::new(0, 0)));
+        assert_eq!(Some(&'c'), grid.get(IVec2::new(2, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        assert_eq!(
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            }),
+            Grid::parse("abc\nde", YAxis::Down, |ch| ch)
+        );
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum CellError {
+        Grid(GridError),
+        Invalid(SourceSpan),
+    }
+
+    impl From<GridError> for CellError {
+        fn from(error: GridError) -> Self {
+            CellError::Grid(error)
+        }
+    }
+
+    #[test]
+    fn test_try_parse_reports_cell_span() {
+        let parse = |input| {
+            Grid::try_parse(input, YAxis::Up, |ch, span| match ch {
+                '.' | '#' => Ok(ch),
+                _ => Err(CellError::Invalid(span)),
+            })
+        };
+        assert_eq!(
+            Err(CellError::Invalid(SourceSpan::from((7, 1)))),
+            parse("\n.#.\r\n.x.\n")
+        );
+        assert!(matches!(
+            parse(".#.\n.."),
+            Err(CellError::Grid(GridError::Ragged { row: 1, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(
+            Err(GridError::Empty),
+            Grid::parse("\n\n", YAxis::Down, |ch| ch)
+        );
+    }
+
+    #[test]
+    fn test_in_bounds_true() -> miette::Result<()> {
+        let grid = Grid::parse("...\n...\n...", YAxis::Up, |ch| ch)?;
+        for position in [(0, 0), (0, 1), (2, 2)] {
+            tracing::trace!("Checking position: {:?} in bounds", position);
+            assert!(grid.in_bounds(IVec2::new(position.0, position.1)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_bounds_false() -> miette::Result<()> {
+        let grid = Grid::parse("...\n...\n...", YAxis::Up, |ch| ch)?;
+        for position in [(-1, 0), (0, -1), (3, 2), (2, 3), (3, 3)] {
+            tracing::trace!("Checking position: {:?} not in bounds", position);
+            assert!(!grid.in_bounds(IVec2::new(position.0, position.1)));
+            assert_eq!(None, grid.get(IVec2::new(position.0, position.1)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbours_at_corner() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, YAxis::Down, |ch| ch)?;
+        let orthogonal: Vec<char> = grid.neighbours4(IVec2::ZERO).map(|(_, ch)| *ch).collect();
+        assert_eq!(vec!['b', 'd'], orthogonal);
+        assert_eq!(3, grid.neighbours8(IVec2::ZERO).count());
+        assert_eq!(5, grid.neighbours8(IVec2::new(1, 0)).count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_step_follows_y_axis() -> miette::Result<()> {
+        let down = Grid::parse(INPUT, YAxis::Down, |ch| ch)?;
+        let up = Grid::parse(INPUT, YAxis::Up, |ch| ch)?;
+        let start = IVec2::new(1, 0);
+        assert_eq!(Some(&'e'), down.get(down.step(start, Direction::South)));
+        assert_eq!(Some(&'b'), up.get(up.step(start, Direction::North)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_and_positions() -> miette::Result<()> {
+        let grid = Grid::parse("#.#\n.#.", YAxis::Up, |ch| ch == '#')?;
+        assert_eq!(Some(IVec2::new(0, 1)), grid.find(|wall| *wall));
+        assert_eq!(
+            vec![IVec2::new(0, 1), IVec2::new(2, 1), IVec2::new(1, 0)],
+            grid.positions(|wall| *wall).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use std::ops::Deref;
+
+use miette::{NamedSource, SourceSpan};
+
+/// Puzzle input with the quirks of copied and downloaded text smoothed over.
+///
+/// Line endings may be `\n` or `\r\n`, trailing whitespace is dropped from every line, and blank
+/// lines before and after the puzzle are skipped. Lines borrow from the original text and keep
+/// their byte offset into it, so diagnostics can point at the input exactly as it was given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    original: &'a str,
+    lines: Vec<Line<'a>>,
+}
+
+/// One line of the input without its line ending or trailing whitespace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    /// Byte offset of the start of the line in the original text.
+    pub offset: usize,
+    /// Line number in the original text, starting at 1.
+    pub number: usize,
+}
+
+/// A run of non-blank lines, such as day 5's ordering rules or its updates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section<'i, 'a> {
+    lines: &'i [Line<'a>],
+}
+
+impl<'a> Input<'a> {
+    pub fn new(original: &'a str) -> Self {
+        let mut lines = vec![];
+        let mut offset = 0;
+        for (index, raw) in original.split_inclusive('\n').enumerate() {
+            lines.push(Line {
+                text: raw.trim_end(),
+                offset,
+                number: index + 1,
+            });
+            offset += raw.len();
+        }
+
+        let start = lines
+            .iter()
+            .position(|line| !line.is_empty())
+            .unwrap_or(lines.len());
+        let end = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(start, |last| last + 1);
+        lines.truncate(end);
+        lines.drain(..start);
+
+        Input { original, lines }
+    }
+
+    /// The text exactly as it was given, for use as diagnostic source code.
+    pub fn original(&self) -> &'a str {
+        self.original
+    }
+
+    /// The original text as source code for a diagnostic, so spans from [`Line`] and
+    /// [`Section`] can be labelled.
+    pub fn source(&self) -> NamedSource<String> {
+        source(self.original)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Every line between the first and last non-blank lines, blank lines inside included.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> + '_ {
+        self.lines.iter().copied()
+    }
+
+    /// Runs of lines separated by one or more blank lines.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_, 'a>> {
+        self.lines
+            .split(|line| line.is_empty())
+            .filter(|lines| !lines.is_empty())
+            .map(|lines| Section { lines })
+    }
+
+    /// The normalised text, lines joined with `\n` and no trailing newline.
+    pub fn text(&self) -> String {
+        join(&self.lines)
+    }
+}
+
+impl<'a> Line<'a> {
+    /// Span of the whole line in the original text.
+    pub fn span(&self) -> SourceSpan {
+        (self.offset, self.text.len()).into()
+    }
+
+    /// Span in the original text of `part`, which must be a slice of this line's text, as
+    /// returned by `split` or `trim`.
+    pub fn span_of(&self, part: &str) -> SourceSpan {
+        let start = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(
+            start + part.len() <= self.text.len(),
+            "part is outside the line"
+        );
+        (self.offset + start, part.len()).into()
+    }
+}
+
+impl Deref for Line<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+impl<'i, 'a> Section<'i, 'a> {
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> + 'i {
+        self.lines.iter().copied()
+    }
+
+    /// Span from the start of the first line to the end of the last one in the original text.
+    pub fn span(&self) -> SourceSpan {
+        let first = self.lines[0];
+        let last = self.lines[self.lines.len() - 1];
+        (first.offset, last.offset + last.text.len() - first.offset).into()
+    }
+
+    /// The section's normalised text, lines joined with `\n`.
+    pub fn text(&self) -> String {
+        join(self.lines)
+    }
+}
+
+/// Puzzle input as diagnostic source code, for days that don't go through [`Input`].
+pub fn source(original: &str) -> NamedSource<String> {
+    NamedSource::new("puzzle input", original.to_string())
+}
+
+fn join(lines: &[Line]) -> String {
+    lines
+        .iter()
+        .map(|line| line.text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_normalises_line_endings_and_whitespace() {
+        let input = Input::new("\r\n\n3   4  \r\n4   3\r\n\r\n");
+        assert_eq!(
+            vec!["3   4", "4   3"],
+            input.lines().map(|line| line.text).collect::<Vec<_>>()
+        );
+        assert_eq!("3   4\n4   3", input.text());
+    }
+
+    #[test]
+    fn test_lines_keep_original_offsets() {
+        let original = "\n\r\nab  \r\ncd,ef\n";
+        let input = Input::new(original);
+        let lines: Vec<Line> = input.lines().collect();
+
+        assert_eq!(3, lines[0].number);
+        assert_eq!("ab", &original[3..5]);
+        assert_eq!(SourceSpan::from((3, 2)), lines[0].span());
+
+        let ef = lines[1].split(',').nth(1).expect("second field");
+        assert_eq!(SourceSpan::from((12, 2)), lines[1].span_of(ef));
+        assert_eq!("ef", &original[12..14]);
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = Input::new("\n47|53\n97|13\r\n\r\n\n75,47,61\n97,61\n");
+        let sections: Vec<Section> = input.sections().collect();
+
+        assert_eq!(2, sections.len());
+        assert_eq!("47|53\n97|13", sections[0].text());
+        assert_eq!(
+            vec!["75,47,61", "97,61"],
+            sections[1]
+                .lines()
+                .map(|line| line.text)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(SourceSpan::from((1, 11)), sections[0].span());
+        assert_eq!(
+            "75,47,61\n97,61",
+            &input.original()[17..31],
+            "section span covers the original lines"
+        );
+        assert_eq!(SourceSpan::from((17, 14)), sections[1].span());
+    }
+
+    #[test]
+    fn test_blank_input() {
+        let input = Input::new(" \n\r\n");
+        assert!(input.is_empty());
+        assert_eq!(0, input.sections().count());
+        assert_eq!("", input.text());
+    }
+}
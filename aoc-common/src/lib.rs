@@ -0,0 +1,11 @@
+pub mod answers;
+pub mod bench;
+pub mod cycle;
+pub mod direction;
+pub mod extent;
+pub mod grid;
+pub mod input;
+pub mod numeric;
+pub mod parsers;
+pub mod search;
+pub mod solution;
@@ -0,0 +1,164 @@
+//! Base-10 digit arithmetic on `u64`, for puzzles that treat numbers as strings of digits.
+//!
+//! Everything here works on the value directly instead of formatting and reparsing it, and
+//! anything that can grow past `u64::MAX` returns `None` instead of wrapping.
+
+/// Number of decimal digits in `n`, where 0 has one digit.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// `10^exponent`, or `None` if it doesn't fit in a `u64`.
+pub fn pow10(exponent: u32) -> Option<u64> {
+    10u64.checked_pow(exponent)
+}
+
+/// The digits of `b` written after those of `a`, so `concat(12, 345)` is `12345`.
+pub fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(pow10(digit_count(b))?)?.checked_add(b)
+}
+
+/// The digits of `n` before a trailing `suffix`, undoing [`concat`], so `strip_suffix(12345,
+/// 345)` is `12`. `None` if `n` doesn't end with the digits of `suffix`.
+pub fn strip_suffix(n: u64, suffix: u64) -> Option<u64> {
+    match pow10(digit_count(suffix)) {
+        Some(place) => (n % place == suffix).then_some(n / place),
+        // NOTE: Only a 20 digit suffix has no place, and only itself ends with it
+        None => (n == suffix).then_some(0),
+    }
+}
+
+/// The left and right halves of the digits of `n`, so `split_halves(1000)` is `(10, 0)`. `None`
+/// if `n` has an odd number of digits.
+pub fn split_halves(n: u64) -> Option<(u64, u64)> {
+    let count = digit_count(n);
+    if !count.is_multiple_of(2) {
+        return None;
+    }
+    let half = pow10(count / 2).expect("half the digits of a u64 fit in a u64");
+    Some((n / half, n % half))
+}
+
+/// The digits of `n`, most significant first.
+pub fn digits(n: u64) -> Digits {
+    let count = digit_count(n);
+    Digits {
+        rest: n,
+        divisor: pow10(count - 1).expect("a u64 digit's place fits in a u64"),
+        remaining: count as usize,
+    }
+}
+
+/// `n` with its digits in reverse order, dropping what become leading zeros, so `reverse(120)`
+/// is `21`. `None` if the result doesn't fit, as with `reverse(u64::MAX)`.
+pub fn reverse(n: u64) -> Option<u64> {
+    let mut rest = n;
+    let mut reversed = 0u64;
+    loop {
+        reversed = reversed.checked_mul(10)?.checked_add(rest % 10)?;
+        rest /= 10;
+        if rest == 0 {
+            return Some(reversed);
+        }
+    }
+}
+
+/// Iterator over the digits of a number, see [`digits`].
+#[derive(Clone, Debug)]
+pub struct Digits {
+    rest: u64,
+    divisor: u64,
+    remaining: usize,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let digit = self.rest / self.divisor;
+        self.rest %= self.divisor;
+        self.divisor /= 10;
+        self.remaining -= 1;
+        Some(digit as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_digit_count() {
+        assert_eq!(1, digit_count(0));
+        assert_eq!(1, digit_count(9));
+        assert_eq!(2, digit_count(10));
+        assert_eq!(4, digit_count(2024));
+        assert_eq!(20, digit_count(u64::MAX));
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(Some(156), concat(15, 6));
+        assert_eq!(Some(12345), concat(12, 345));
+        assert_eq!(Some(10), concat(1, 0));
+        assert_eq!(Some(7), concat(0, 7));
+        assert_eq!(Some(u64::MAX), concat(1844674407370955161, 5));
+        assert_eq!(None, concat(1844674407370955161, 6));
+        assert_eq!(None, concat(1, u64::MAX));
+    }
+
+    #[test]
+    fn test_strip_suffix() {
+        assert_eq!(Some(12), strip_suffix(12345, 345));
+        assert_eq!(Some(15), strip_suffix(156, 6));
+        assert_eq!(Some(0), strip_suffix(6, 6));
+        assert_eq!(Some(1), strip_suffix(10, 0));
+        assert_eq!(None, strip_suffix(156, 5));
+        assert_eq!(None, strip_suffix(6, 16));
+        assert_eq!(Some(0), strip_suffix(u64::MAX, u64::MAX));
+        assert_eq!(None, strip_suffix(u64::MAX - 1, u64::MAX));
+        for (a, b) in [(81, 40), (1, 0), (0, 7), (486, 6)] {
+            assert_eq!(Some(a), concat(a, b).and_then(|n| strip_suffix(n, b)));
+        }
+    }
+
+    #[test]
+    fn test_split_halves() {
+        assert_eq!(Some((10, 0)), split_halves(1000));
+        assert_eq!(Some((253, 0)), split_halves(253000));
+        assert_eq!(Some((1, 7)), split_halves(17));
+        assert_eq!(None, split_halves(0));
+        assert_eq!(None, split_halves(125));
+        assert_eq!(Some((1844674407, 3709551615)), split_halves(u64::MAX));
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(vec![0], digits(0).collect::<Vec<_>>());
+        assert_eq!(vec![2, 0, 2, 4], digits(2024).collect::<Vec<_>>());
+        assert_eq!(20, digits(u64::MAX).len());
+        assert_eq!(Some(1), digits(u64::MAX).next());
+    }
+
+    #[test]
+    fn test_reverse() {
+        assert_eq!(Some(0), reverse(0));
+        assert_eq!(Some(4202), reverse(2024));
+        assert_eq!(Some(21), reverse(120));
+        assert_eq!(
+            Some(9_999_999_999_999_999_991),
+            reverse(1_999_999_999_999_999_999)
+        );
+        assert_eq!(None, reverse(u64::MAX));
+    }
+}
@@ -0,0 +1,252 @@
+use std::str::FromStr;
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use nom::{
+    character::complete::{char, digit1, one_of, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::{pair, separated_pair},
+    IResult, Parser,
+};
+use thiserror::Error;
+
+use crate::grid::{Grid, GridError, YAxis};
+use crate::input::{self, Input, Line};
+
+/// A parser failure located in the original puzzle input.
+#[derive(Error, Diagnostic, Debug)]
+pub enum ParseError {
+    #[error("Expected {expected} on line {line}")]
+    Line {
+        expected: &'static str,
+        line: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("could not read this")]
+        span: SourceSpan,
+    },
+    #[error("Unexpected {found:?} in {expected}")]
+    Cell {
+        found: char,
+        expected: &'static str,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("not part of {expected}")]
+        span: SourceSpan,
+    },
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Grid(#[from] GridError),
+}
+
+impl ParseError {
+    /// Where in the original input the error points, if anywhere.
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            ParseError::Line { span, .. } | ParseError::Cell { span, .. } => Some(*span),
+            ParseError::Grid(_) => None,
+        }
+    }
+}
+
+/// Line number and span of the [`ParseError::Line`] in `error`, for tests that check where a
+/// parse failed. Panics on any other error.
+pub fn line_error(error: &miette::Report) -> (usize, SourceSpan) {
+    match error.downcast_ref() {
+        Some(ParseError::Line { line, span, .. }) => (*line, *span),
+        _ => panic!("expected a line error, got {error:?}"),
+    }
+}
+
+/// Unexpected character and span of the [`ParseError::Cell`] in `error`, for tests that check
+/// where a parse failed. Panics on any other error.
+pub fn cell_error(error: &miette::Report) -> (char, SourceSpan) {
+    match error.downcast_ref() {
+        Some(ParseError::Cell { found, span, .. }) => (*found, *span),
+        _ => panic!("expected a cell error, got {error:?}"),
+    }
+}
+
+/// Parse a whole line with `parser`, pointing at the word where it stopped when it fails or
+/// leaves something unread. Words are separated by spaces or commas. `expected` describes the
+/// line, such as "an ordering rule like `47|53`".
+pub fn line<'a, O>(
+    input: &Input<'a>,
+    line: Line<'a>,
+    expected: &'static str,
+    parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> Result<O, ParseError> {
+    let rest = match all_consuming(parser)(line.text) {
+        Ok((_, output)) => return Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => e.input,
+        Err(nom::Err::Incomplete(_)) => &line.text[line.len()..],
+    }
+    .trim_start();
+    // NOTE: nom stops inside a word like `4o`, so widen the span to the whole word
+    let separator = |ch: char| ch == ' ' || ch == ',';
+    let stopped = line.len() - rest.len();
+    let start = line.text[..stopped].rfind(separator).map_or(0, |at| at + 1);
+    let end = stopped + rest.find(separator).unwrap_or(rest.len());
+    Err(ParseError::Line {
+        expected,
+        line: line.number,
+        input: input.source(),
+        span: line.span_of(&line.text[start..end]),
+    })
+}
+
+/// Parse every non-blank line with `parser`, see [`line`].
+pub fn lines<'a, O>(
+    input: &Input<'a>,
+    lines: impl IntoIterator<Item = Line<'a>>,
+    expected: &'static str,
+    mut parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> Result<Vec<O>, ParseError> {
+    lines
+        .into_iter()
+        .filter(|current| !current.is_empty())
+        .map(|current| line(input, current, expected, |text| parser.parse(text)))
+        .collect()
+}
+
+/// Parse a line of single digits, such as day 9's disk map `2333133121414131402`, pointing at
+/// the first character that isn't one. `expected` names the line, such as "a disk map".
+pub fn digits<'a>(
+    input: &Input<'a>,
+    line: Line<'a>,
+    expected: &'static str,
+) -> Result<Vec<u32>, ParseError> {
+    line.char_indices()
+        .map(|(index, found)| {
+            found.to_digit(10).ok_or_else(|| ParseError::Cell {
+                found,
+                expected,
+                input: input.source(),
+                span: (line.offset + index, found.len_utf8()).into(),
+            })
+        })
+        .collect()
+}
+
+/// Parse a character grid, rejecting characters `cell` doesn't recognise. `expected` names the
+/// grid, such as "the lab map".
+pub fn grid<T>(
+    input: &str,
+    y_axis: YAxis,
+    expected: &'static str,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, ParseError> {
+    Grid::try_parse(input, y_axis, |found, span| {
+        cell(found).ok_or_else(|| ParseError::Cell {
+            found,
+            expected,
+            input: input::source(input),
+            span,
+        })
+    })
+}
+
+/// A decimal integer with an optional sign, such as `-12`.
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Integers separated by spaces, such as a day 2 report `7 6 4 2 1`.
+pub fn integers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, integer)(input)
+}
+
+/// Exactly two integers separated by spaces, such as day 1's `3   4`.
+pub fn column_pair<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(integer, space1, integer)(input)
+}
+
+/// Two integers separated by `|`, such as day 5's `47|53`.
+pub fn rule<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(integer, char('|'), integer)(input)
+}
+
+/// Integers separated by commas, such as day 5's `75,47,61`.
+pub fn comma_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(char(','), integer)(input)
+}
+
+/// A target followed by its operands, such as day 7's `3267: 81 40 27`.
+pub fn equation<T: FromStr>(input: &str) -> IResult<&str, (T, Vec<T>)> {
+    separated_pair(integer, pair(char(':'), space1), integers)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_shapes() {
+        assert_eq!(Ok(("", (3u32, 4u32))), column_pair("3   4"));
+        assert_eq!(Ok(("", vec![7i64, -6, 4])), integers("7 -6 4"));
+        assert_eq!(Ok(("", (47usize, 53usize))), rule("47|53"));
+        assert_eq!(Ok(("", vec![75u8, 47, 61])), comma_list("75,47,61"));
+        assert_eq!(
+            Ok(("", (3267u64, vec![81, 40, 27]))),
+            equation("3267: 81 40 27")
+        );
+    }
+
+    #[test]
+    fn test_lines_locate_errors() {
+        let input = Input::new("\n3   4\r\n4   x3\n");
+        let error = lines(&input, input.lines(), "two ids", column_pair::<u32>).unwrap_err();
+        assert!(matches!(error, ParseError::Line { line: 3, .. }));
+        assert_eq!(Some(SourceSpan::from((12, 2))), error.span());
+    }
+
+    #[test]
+    fn test_line_points_at_whole_word() {
+        let input = Input::new("3267: 81 4o 27");
+        let current = input.lines().next().expect("one line");
+        let error = line(&input, current, "an equation", equation::<u64>).unwrap_err();
+        assert_eq!(Some(SourceSpan::from((9, 2))), error.span());
+    }
+
+    #[test]
+    fn test_line_rejects_leftovers() {
+        let input = Input::new("75,47,61 12");
+        let current = input.lines().next().expect("one line");
+        let error = line(&input, current, "pages", comma_list::<u32>).unwrap_err();
+        assert_eq!(Some(SourceSpan::from((9, 2))), error.span());
+    }
+
+    #[test]
+    fn test_integer_overflow_is_an_error() {
+        let input = Input::new("300");
+        let error = lines(&input, input.lines(), "a byte", integer::<u8>).unwrap_err();
+        assert_eq!(Some(SourceSpan::from((0, 3))), error.span());
+    }
+
+    #[test]
+    fn test_digits_locate_bad_digit() -> Result<(), ParseError> {
+        let input = Input::new("\n2333x3\n");
+        let current = input.lines().next().expect("one line");
+        let error = digits(&input, current, "a disk map").unwrap_err();
+        assert!(matches!(error, ParseError::Cell { found: 'x', .. }));
+        assert_eq!(Some(SourceSpan::from((5, 1))), error.span());
+
+        let input = Input::new("2024");
+        let current = input.lines().next().expect("one line");
+        assert_eq!(vec![2, 0, 2, 4], digits(&input, current, "a disk map")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_rejects_unknown_cells() {
+        let error = grid(".#\n#?", YAxis::Down, "a map", |ch| match ch {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })
+        .unwrap_err();
+        assert!(matches!(error, ParseError::Cell { found: '?', .. }));
+        assert_eq!(Some(SourceSpan::from((4, 1))), error.span());
+    }
+}
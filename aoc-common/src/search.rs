@@ -0,0 +1,325 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Every state a search reached, with the cost of the cheapest way there and the state it was
+/// reached from, so paths can be rebuilt.
+#[derive(Clone, Debug)]
+pub struct Paths<S, C> {
+    costs: HashMap<S, C>,
+    parents: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Paths<S, C> {
+    fn new(start: S, zero: C) -> Self {
+        Paths {
+            costs: HashMap::from([(start, zero)]),
+            parents: HashMap::new(),
+        }
+    }
+
+    /// Cost of the cheapest path to `state`, or `None` if it wasn't reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// Every reached state with its cost, the start included, in no particular order.
+    pub fn reached(&self) -> impl Iterator<Item = (&S, C)> {
+        self.costs.iter().map(|(state, cost)| (state, *cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    /// States from the start to `goal` inclusive, or `None` if `goal` wasn't reached.
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(goal) {
+            return None;
+        }
+        let mut path = vec![goal.clone()];
+        while let Some(parent) = self.parents.get(path.last().expect("path is never empty")) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from `start`, where every step costs 1.
+pub fn bfs<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        let distance = paths.costs[&state] + 1;
+        for next in neighbours(&state) {
+            if let Entry::Vacant(entry) = paths.costs.entry(next.clone()) {
+                entry.insert(distance);
+                paths.parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Number of distinct paths from `start` to any state matching `is_goal`, found depth first.
+/// Goals end a path. The graph must not have cycles reachable from `start`, as counts are
+/// memoised per state.
+pub fn count_paths<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> u64
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    fn count<S, I>(
+        state: &S,
+        neighbours: &mut impl FnMut(&S) -> I,
+        is_goal: &mut impl FnMut(&S) -> bool,
+        memo: &mut HashMap<S, u64>,
+    ) -> u64
+    where
+        S: Clone + Eq + Hash,
+        I: IntoIterator<Item = S>,
+    {
+        if is_goal(state) {
+            return 1;
+        }
+        if let Some(known) = memo.get(state) {
+            return *known;
+        }
+        let total = neighbours(state)
+            .into_iter()
+            .map(|next| count(&next, neighbours, is_goal, memo))
+            .sum();
+        memo.insert(state.clone(), total);
+        total
+    }
+
+    count(&start, &mut neighbours, &mut is_goal, &mut HashMap::new())
+}
+
+/// Cheapest cost to every state reachable from `start`, where `neighbours` gives each next state
+/// with the cost of stepping to it. Costs must not be negative, and `C::default()` is zero.
+pub fn dijkstra<S, C, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (paths, _) = best_first(start, neighbours, |_| C::default(), |_| false);
+    paths
+}
+
+/// Cheapest path from `start` to a state matching `is_goal`, returning its cost and states.
+/// `heuristic` must never overestimate the remaining cost, such as the manhattan distance on a
+/// grid; a heuristic of zero makes this Dijkstra's algorithm stopping at the first goal.
+pub fn astar<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (paths, goal) = best_first(start, neighbours, heuristic, is_goal);
+    let goal = goal?;
+    let cost = paths.cost(&goal)?;
+    Some((cost, paths.path_to(&goal)?))
+}
+
+/// State waiting in the open set, ordered so the `BinaryHeap` pops the lowest priority first.
+struct Open<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Open<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Open<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Open<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Open<S, C> {}
+
+/// Shared loop of Dijkstra and A*, stopping early at the first goal popped.
+fn best_first<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Paths<S, C>, Option<S>)
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let zero = C::default();
+    let mut paths = Paths::new(start.clone(), zero);
+    let mut open = BinaryHeap::from([Open {
+        priority: heuristic(&start),
+        cost: zero,
+        state: start,
+    }]);
+
+    while let Some(Open { cost, state, .. }) = open.pop() {
+        if paths.costs.get(&state).is_some_and(|best| *best < cost) {
+            // NOTE: A cheaper route to this state was queued after this entry
+            continue;
+        }
+        if is_goal(&state) {
+            return (paths, Some(state));
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if paths
+                .costs
+                .get(&next)
+                .is_some_and(|best| *best <= next_cost)
+            {
+                continue;
+            }
+            paths.costs.insert(next.clone(), next_cost);
+            paths.parents.insert(next.clone(), state.clone());
+            open.push(Open {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+    (paths, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, YAxis};
+    use glam::IVec2;
+    use test_log::test;
+
+    const MAZE: &str = "
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E
+";
+
+    fn open_neighbours(grid: &Grid<char>, position: IVec2) -> Vec<IVec2> {
+        grid.neighbours4(position)
+            .filter(|(_, cell)| **cell != '#')
+            .map(|(next, _)| next)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs_distances_and_path() {
+        let grid = Grid::parse(MAZE, YAxis::Down, |ch| ch).expect("maze parses");
+        let start = grid.find(|cell| *cell == 'S').expect("start");
+        let end = grid.find(|cell| *cell == 'E').expect("end");
+
+        let paths = bfs(start, |position| open_neighbours(&grid, *position));
+
+        assert_eq!(Some(0), paths.cost(&start));
+        assert_eq!(Some(15), paths.cost(&end));
+        let path = paths.path_to(&end).expect("end is reachable");
+        assert_eq!(16, path.len());
+        assert_eq!((start, end), (path[0], path[15]));
+        for step in path.windows(2) {
+            assert_eq!(1, (step[1] - step[0]).abs().element_sum());
+        }
+        assert_eq!(None, paths.cost(&IVec2::new(3, 0)));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // NOTE: Two ways from 1 to 4 and on to 6, plus the direct edge 1 -> 6
+        let edges = HashMap::from([
+            (1, vec![2, 3, 6]),
+            (2, vec![4]),
+            (3, vec![4]),
+            (4, vec![5, 6]),
+            (5, vec![6]),
+        ]);
+        let next = |node: &u32| edges.get(node).cloned().unwrap_or_default();
+        assert_eq!(5, count_paths(1, next, |node| *node == 6));
+        assert_eq!(0, count_paths(1, next, |node| *node == 7));
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheap_detour() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 7), ('c', 1)]),
+            ('c', vec![('d', 2)]),
+            ('d', vec![('b', 1)]),
+            ('b', vec![('e', 1)]),
+        ]);
+        let paths = dijkstra('a', |node| edges.get(node).cloned().unwrap_or_default());
+
+        assert_eq!(Some(4u32), paths.cost(&'b'));
+        assert_eq!(Some(5), paths.cost(&'e'));
+        assert_eq!(Some(vec!['a', 'c', 'd', 'b', 'e']), paths.path_to(&'e'));
+        assert_eq!(5, paths.len());
+    }
+
+    #[test]
+    fn test_astar_matches_bfs() {
+        let grid = Grid::parse(MAZE, YAxis::Down, |ch| ch).expect("maze parses");
+        let start = grid.find(|cell| *cell == 'S').expect("start");
+        let end = grid.find(|cell| *cell == 'E').expect("end");
+
+        let (cost, path) = astar(
+            start,
+            |position| {
+                open_neighbours(&grid, *position)
+                    .into_iter()
+                    .map(|next| (next, 1u32))
+            },
+            |position| (end - *position).abs().element_sum() as u32,
+            |position| *position == end,
+        )
+        .expect("end is reachable");
+
+        assert_eq!(15, cost);
+        assert_eq!(Some(end), path.last().copied());
+        assert_eq!(
+            None,
+            astar(
+                start,
+                |_: &IVec2| Vec::<(IVec2, u32)>::new(),
+                |_| 0,
+                |position| *position == end
+            )
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Which half of a day's puzzle to solve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+#[derive(Error, Diagnostic, Debug, PartialEq, Eq)]
+#[error("Invalid puzzle part {0}, expected 1 or 2")]
+pub struct InvalidPart(pub u8);
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = InvalidPart;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            other => Err(InvalidPart(other)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A puzzle answer, as it would be submitted.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    Text(String),
+    MultiLine(Vec<String>),
+}
+
+impl Answer {
+    /// Numeric value of an integer answer, for comparing against too high/low bounds.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(value) => Some(*value as i128),
+            Answer::BigInteger(value) => Some(*value),
+            Answer::Text(_) | Answer::MultiLine(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::BigInteger(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+            Answer::MultiLine(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    match i64::try_from(value) {
+                        Ok(value) => Answer::Integer(value),
+                        Err(_) => Answer::BigInteger(value as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::MultiLine(lines)
+    }
+}
+
+/// One part solved by [`Solution::solve_parts`].
+#[derive(Debug)]
+pub struct Solved {
+    pub part: Part,
+    pub answer: miette::Result<Answer>,
+    /// Time spent solving the part, not counting the shared parse.
+    pub elapsed: Duration,
+    /// The part's breakdown when one was asked for, otherwise empty.
+    pub breakdown: miette::Result<Vec<String>>,
+}
+
+/// A day's puzzle: parse the input once, then solve either part from the parsed form.
+pub trait Solution {
+    /// Parsed puzzle input, shared by both parts.
+    type Input;
+    /// Typed answer returned by both parts.
+    type Answer: Into<Answer>;
+    /// Parameters that differ between the worked examples and the real puzzle, such as
+    /// iteration counts. The `Default` must be the real puzzle's values.
+    type Config: Default;
+
+    fn parse(input: &str) -> miette::Result<Self::Input>;
+
+    fn part1(input: &Self::Input, config: &Self::Config) -> miette::Result<Self::Answer>;
+
+    fn part2(input: &Self::Input, config: &Self::Config) -> miette::Result<Self::Answer>;
+
+    /// Lines explaining how a part's answer comes about, such as what each line of the input
+    /// contributed, for `aoc run --breakdown`. Most days have nothing to add.
+    fn breakdown(
+        _input: &Self::Input,
+        _part: Part,
+        _config: &Self::Config,
+    ) -> miette::Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    /// Parse the raw input and solve one part with the default configuration.
+    fn solve(input: &str, part: Part) -> miette::Result<Answer> {
+        Self::solve_with(input, part, &Self::Config::default())
+    }
+
+    fn solve_with(input: &str, part: Part, config: &Self::Config) -> miette::Result<Answer> {
+        let input = Self::parse(input)?;
+        let answer = match part {
+            Part::One => Self::part1(&input, config)?,
+            Part::Two => Self::part2(&input, config)?,
+        };
+        Ok(answer.into())
+    }
+
+    /// Parse the raw input once and solve each of `parts` from it with the default
+    /// configuration, also breaking them down when `breakdown` is set. Only a parse failure is
+    /// an error here, a part that fails is reported in its [`Solved`].
+    fn solve_parts(input: &str, parts: &[Part], breakdown: bool) -> miette::Result<Vec<Solved>> {
+        let config = Self::Config::default();
+        let input = Self::parse(input)?;
+        Ok(parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => Self::part1(&input, &config),
+                    Part::Two => Self::part2(&input, &config),
+                };
+                let elapsed = start.elapsed();
+                Solved {
+                    part,
+                    answer: answer.map(Into::into),
+                    elapsed,
+                    breakdown: if breakdown {
+                        Self::breakdown(&input, part, &config)
+                    } else {
+                        Ok(vec![])
+                    },
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        type Input = Vec<u64>;
+        type Answer = u64;
+        type Config = u64;
+
+        fn parse(input: &str) -> miette::Result<Self::Input> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|e| miette::miette!("{e}")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+            Ok(input.iter().sum::<u64>() * 2)
+        }
+
+        fn part2(input: &Self::Input, config: &Self::Config) -> miette::Result<Self::Answer> {
+            Ok(input.iter().sum::<u64>() * config)
+        }
+    }
+
+    #[test]
+    fn test_solve() -> miette::Result<()> {
+        assert_eq!(Answer::Integer(6), Doubler::solve("1 2", Part::One)?);
+        assert_eq!(Answer::Integer(0), Doubler::solve("1 2", Part::Two)?);
+        assert_eq!(
+            Answer::Integer(30),
+            Doubler::solve_with("1 2", Part::Two, &10)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_parts() -> miette::Result<()> {
+        let solved = Doubler::solve_parts("1 2", &Part::BOTH, true)?;
+        assert_eq!(
+            vec![Part::One, Part::Two],
+            solved.iter().map(|s| s.part).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Answer::Integer(6),
+            *solved[0].answer.as_ref().expect("part 1 solves")
+        );
+        assert_eq!(
+            Answer::Integer(0),
+            *solved[1].answer.as_ref().expect("part 2 solves")
+        );
+        assert!(solved[0]
+            .breakdown
+            .as_ref()
+            .expect("no breakdown")
+            .is_empty());
+        assert!(Doubler::solve_parts("1 x", &Part::BOTH, false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer_conversions() {
+        assert_eq!(Answer::Integer(42), Answer::from(42u32));
+        assert_eq!(Answer::BigInteger(u64::MAX as i128), Answer::from(u64::MAX));
+        assert_eq!(Answer::Integer(-3), Answer::from(-3i128));
+        assert_eq!(Answer::Text("abc".to_string()), Answer::from("abc"));
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("18446744073709551615", Answer::from(u64::MAX).to_string());
+        assert_eq!(
+            "#..\n.#.",
+            Answer::from(vec!["#..".to_string(), ".#.".to_string()]).to_string()
+        );
+    }
+
+    #[test]
+    fn test_part_from_number() {
+        assert_eq!(Ok(Part::Two), Part::try_from(2));
+        assert_eq!(Err(InvalidPart(3)), Part::try_from(3));
+    }
+}
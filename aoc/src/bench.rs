@@ -0,0 +1,469 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use miette::{Diagnostic, IntoDiagnostic, WrapErr};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::registry::workspace_root;
+
+/// History file kept at the workspace root.
+const HISTORY_FILE: &str = "bench-history.json";
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum BenchError {
+    #[error("`cargo bench` exited with {0}")]
+    CargoFailed(std::process::ExitStatus),
+    #[error("No divan results found in the benchmark output")]
+    #[diagnostic(help("check that the benches ran, e.g. that their input files exist"))]
+    NoResults,
+    #[error("No recorded runs to report on")]
+    #[diagnostic(help("record one with `aoc bench record`"))]
+    EmptyHistory,
+    #[error("No recorded run with baseline {0:?}")]
+    UnknownBaseline(String),
+    #[error("{count} regression(s) beyond {threshold}%")]
+    Regressions { count: usize, threshold: f64 },
+}
+
+/// One divan benchmark result, with times in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// Bench target the result came from, such as `day_01_bench`.
+    pub target: String,
+    /// Benchmark path within the target, with nested groups joined by `/`.
+    pub name: String,
+    pub fastest_ns: f64,
+    pub median_ns: f64,
+    pub mean_ns: f64,
+    pub samples: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub commit: String,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+    pub results: Vec<Measurement>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+/// Which run the latest one is compared against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reference {
+    Previous,
+    Baseline(String),
+}
+
+impl History {
+    pub fn path() -> PathBuf {
+        workspace_root().join(HISTORY_FILE)
+    }
+
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {}", path.display()))?;
+        serde_json::from_str(&text)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let text = serde_json::to_string_pretty(self).into_diagnostic()?;
+        std::fs::write(path, text + "\n")
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write {}", path.display()))
+    }
+
+    /// The latest run and the run it should be compared with, if any.
+    pub fn compare(&self, reference: &Reference) -> Result<(&Run, Option<&Run>), BenchError> {
+        let (latest, earlier) = self.runs.split_last().ok_or(BenchError::EmptyHistory)?;
+        let against = match reference {
+            Reference::Previous => earlier.last(),
+            Reference::Baseline(name) => Some(
+                self.runs
+                    .iter()
+                    .rev()
+                    .find(|run| run.baseline.as_ref() == Some(name))
+                    .ok_or_else(|| BenchError::UnknownBaseline(name.clone()))?,
+            ),
+        };
+        Ok((latest, against))
+    }
+}
+
+pub struct RecordOptions {
+    pub package: Option<String>,
+    pub filter: Option<String>,
+    pub baseline: Option<String>,
+    /// Parse previously captured divan output instead of running `cargo bench`.
+    pub from: Option<PathBuf>,
+    /// Commit the results were measured at, for output captured before it was recorded.
+    pub commit: Option<String>,
+}
+
+/// Run the benches, or read saved output, and append the results to the history.
+pub fn record(options: RecordOptions, threshold: f64) -> miette::Result<()> {
+    let output = match &options.from {
+        Some(path) => std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {}", path.display()))?,
+        None => cargo_bench(options.package.as_deref(), options.filter.as_deref())?,
+    };
+    let results = parse_divan(&output);
+    if results.is_empty() {
+        return Err(BenchError::NoResults.into());
+    }
+
+    let (commit, dirty) = match options.commit {
+        Some(commit) => (commit, false),
+        None => git_state(),
+    };
+    let run = Run {
+        commit,
+        dirty,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+        baseline: options.baseline,
+        results,
+    };
+
+    let path = History::path();
+    let mut history = History::load(&path)?;
+    history.runs.push(run);
+    history.save(&path)?;
+
+    let (latest, against) = history.compare(&Reference::Previous)?;
+    print_and_gate(latest, against, threshold)
+}
+
+pub fn report(reference: Reference, threshold: f64) -> miette::Result<()> {
+    let history = History::load(&History::path())?;
+    let (latest, against) = history.compare(&reference)?;
+    print_and_gate(latest, against, threshold)
+}
+
+/// Print the table and fail on regressions, so CI can stop on them.
+fn print_and_gate(latest: &Run, against: Option<&Run>, threshold: f64) -> miette::Result<()> {
+    print!("{}", render(latest, against, threshold));
+    match regressions(latest, against, threshold) {
+        0 => Ok(()),
+        count => Err(BenchError::Regressions { count, threshold }.into()),
+    }
+}
+
+fn cargo_bench(package: Option<&str>, filter: Option<&str>) -> miette::Result<String> {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or("cargo".to_string()));
+    command.current_dir(workspace_root()).args(["bench", "-q"]);
+    if let Some(package) = package {
+        command.args(["-p", package]);
+    }
+    if let Some(filter) = filter {
+        command.args(["--", filter]);
+    }
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .into_diagnostic()
+        .wrap_err("run cargo bench")?;
+    if !output.status.success() {
+        return Err(BenchError::CargoFailed(output.status).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Short hash of `HEAD` and whether the tree is dirty, or "unknown" outside a git checkout.
+fn git_state() -> (String, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(workspace_root())
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "--short", "HEAD"]).unwrap_or("unknown".to_string());
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+    (commit, dirty)
+}
+
+/// Extract results from divan's tree output, ignoring the libtest noise around it.
+pub fn parse_divan(output: &str) -> Vec<Measurement> {
+    let mut results: Vec<Measurement> = vec![];
+    let mut target = String::new();
+    let mut groups: Vec<String> = vec![];
+
+    for line in output.lines() {
+        if line.contains("fastest") && line.contains('│') {
+            target = line
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            groups.clear();
+            continue;
+        }
+        let Some((depth, rest)) = tree_entry(line) else {
+            continue;
+        };
+        let columns: Vec<&str> = rest.split('│').map(str::trim).collect();
+        groups.truncate(depth);
+
+        // NOTE: Group rows have a name but empty statistics columns
+        if columns.len() < 5 || columns[2].is_empty() {
+            groups.push(columns[0].to_string());
+            continue;
+        }
+        // NOTE: The first column holds both the name and the fastest time, e.g. "part1   45.87 µs"
+        let tokens: Vec<&str> = columns[0].split_whitespace().collect();
+        if tokens.len() < 3 {
+            continue;
+        }
+        let (name, fastest) = tokens.split_at(tokens.len() - 2);
+        let (Some(fastest), Some(median), Some(mean), Ok(samples)) = (
+            parse_time(&fastest.join(" ")),
+            parse_time(columns[2]),
+            parse_time(columns[3]),
+            columns[4].parse(),
+        ) else {
+            continue;
+        };
+
+        let mut path = groups.clone();
+        path.push(name.join(" "));
+        let measurement = Measurement {
+            target: target.clone(),
+            name: path.join("/"),
+            fastest_ns: fastest,
+            median_ns: median,
+            mean_ns: mean,
+            samples,
+        };
+        // NOTE: Output appended over several runs repeats benchmarks, the last one wins
+        match results
+            .iter_mut()
+            .find(|result| result.target == measurement.target && result.name == measurement.name)
+        {
+            Some(result) => *result = measurement,
+            None => results.push(measurement),
+        }
+    }
+    results
+}
+
+/// Depth and remaining text of a divan tree line such as `│  ╰─ part2  ...`.
+fn tree_entry(line: &str) -> Option<(usize, &str)> {
+    let branch = line.find("├─").or_else(|| line.find("╰─"))?;
+    let depth = line[..branch].chars().count() / 3;
+    Some((depth, &line[branch + "├─".len()..]))
+}
+
+fn parse_time(text: &str) -> Option<f64> {
+    let (value, unit) = text.trim().split_once(' ')?;
+    let value: f64 = value.parse().ok()?;
+    let scale = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(value * scale)
+}
+
+fn format_time(nanos: f64) -> String {
+    match nanos {
+        n if n >= 1e9 => format!("{:.3} s", n / 1e9),
+        n if n >= 1e6 => format!("{:.3} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.3} µs", n / 1e3),
+        n => format!("{n:.1} ns"),
+    }
+}
+
+/// Percentage change in a result's median time since `against`, if it was measured there.
+fn median_change(result: &Measurement, against: Option<&Run>) -> Option<f64> {
+    let previous = against?
+        .results
+        .iter()
+        .find(|old| old.target == result.target && old.name == result.name)?;
+    Some((result.median_ns - previous.median_ns) / previous.median_ns * 100.0)
+}
+
+/// Number of results in `run` whose median slowed down by more than `threshold` percent.
+pub fn regressions(run: &Run, against: Option<&Run>, threshold: f64) -> usize {
+    run.results
+        .iter()
+        .filter(|result| median_change(result, against).is_some_and(|percent| percent > threshold))
+        .count()
+}
+
+/// Markdown table of a run, with the median change against an earlier run. Changes slower than
+/// `threshold` percent are flagged as regressions.
+pub fn render(run: &Run, against: Option<&Run>, threshold: f64) -> String {
+    let mut table = format!(
+        "Benchmarks at {}{}",
+        run.commit,
+        if run.dirty { " (dirty)" } else { "" }
+    );
+    if let Some(against) = against {
+        table += &format!(", compared with {}", against.commit);
+    }
+    table += "\n\n| Bench | Name | Fastest | Median | Mean | Samples | Change |\n";
+    table += "|-------|------|---------|--------|------|---------|--------|\n";
+
+    for result in &run.results {
+        let change = match median_change(result, against) {
+            Some(percent) if percent > threshold => format!("**{percent:+.1}% regression**"),
+            Some(percent) => format!("{percent:+.1}%"),
+            None => "new".to_string(),
+        };
+        table += &format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            result.target,
+            result.name,
+            format_time(result.fastest_ns),
+            format_time(result.median_ns),
+            format_time(result.mean_ns),
+            result.samples,
+            change
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    const OUTPUT: &str = "
+running 2 tests
+ii
+test result: ok. 0 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+day_01_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1      45.87 µs      │ 90.12 µs      │ 46.12 µs      │ 47.16 µs      │ 100     │ 100
+╰─ part2      145.2 µs      │ 191.7 µs      │ 148.6 µs      │ 149.9 µs      │ 100     │ 100
+
+running 0 tests
+
+day_06_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1      2.867 ms      │ 3.44 ms       │ 2.902 ms      │ 2.93 ms       │ 100     │ 100
+╰─ part2                    │               │               │               │         │
+   ├─ naive   1.2 s         │ 1.3 s         │ 1.25 s        │ 1.26 s        │ 10      │ 10
+   ╰─ jump    85 ns         │ 90 ns         │ 86 ns         │ 87 ns         │ 100     │ 1600
+";
+
+    fn run(commit: &str, median_ns: f64) -> Run {
+        Run {
+            commit: commit.to_string(),
+            dirty: false,
+            timestamp: 0,
+            baseline: None,
+            results: vec![Measurement {
+                target: "day_01_bench".to_string(),
+                name: "part1".to_string(),
+                fastest_ns: median_ns,
+                median_ns,
+                mean_ns: median_ns,
+                samples: 100,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_divan() {
+        let results = parse_divan(OUTPUT);
+        let names: Vec<(&str, &str)> = results
+            .iter()
+            .map(|result| (result.target.as_str(), result.name.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("day_01_bench", "part1"),
+                ("day_01_bench", "part2"),
+                ("day_06_bench", "part1"),
+                ("day_06_bench", "part2/naive"),
+                ("day_06_bench", "part2/jump"),
+            ],
+            names
+        );
+        assert_eq!(45_870.0, results[0].fastest_ns.round());
+        assert_eq!(148_600.0, results[1].median_ns.round());
+        assert_eq!(1_260_000_000.0, results[3].mean_ns);
+        assert_eq!(10, results[3].samples);
+    }
+
+    #[test]
+    fn test_parse_divan_keeps_last_repeat() {
+        let results = parse_divan(&format!(
+            "{OUTPUT}\n{}",
+            OUTPUT.replace("45.87 µs", "40 µs")
+        ));
+        assert_eq!(5, results.len());
+        assert_eq!(40_000.0, results[0].fastest_ns);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(Some(85.0), parse_time("85 ns"));
+        assert_eq!(Some(2_500_000.0), parse_time("2.5 ms"));
+        assert_eq!(None, parse_time("2.5"));
+        assert_eq!("46.120 µs", format_time(46_120.0));
+    }
+
+    #[test]
+    fn test_compare_against_baseline() -> miette::Result<()> {
+        let mut baseline = run("aaa", 100.0);
+        baseline.baseline = Some("before".to_string());
+        let history = History {
+            runs: vec![baseline, run("bbb", 200.0), run("ccc", 110.0)],
+        };
+
+        let (latest, previous) = history.compare(&Reference::Previous)?;
+        assert_eq!("ccc", latest.commit);
+        assert_eq!(Some("bbb"), previous.map(|run| run.commit.as_str()));
+
+        let (_, against) = history.compare(&Reference::Baseline("before".to_string()))?;
+        assert_eq!(Some("aaa"), against.map(|run| run.commit.as_str()));
+        assert!(history
+            .compare(&Reference::Baseline("missing".to_string()))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_flags_regressions() {
+        let table = render(&run("new", 125.0), Some(&run("old", 100.0)), 10.0);
+        assert!(table.contains("| day_01_bench | part1 | 125.0 ns | 125.0 ns | 125.0 ns | 100 | **+25.0% regression** |"));
+        assert_eq!(
+            1,
+            regressions(&run("new", 125.0), Some(&run("old", 100.0)), 10.0)
+        );
+
+        let table = render(&run("new", 105.0), Some(&run("old", 100.0)), 10.0);
+        assert!(table.contains("| +5.0% |"));
+        assert!(!table.contains("regression"));
+        assert_eq!(
+            0,
+            regressions(&run("new", 105.0), Some(&run("old", 100.0)), 10.0)
+        );
+        assert_eq!(0, regressions(&run("new", 125.0), None, 10.0));
+    }
+}
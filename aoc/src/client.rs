@@ -0,0 +1,336 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::solution::Part;
+use miette::Diagnostic;
+use thiserror::Error;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Session cookie file read when `AOC_SESSION` isn't set, shared with other AoC tools.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+/// Minimum gap between two requests to the site from one run.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum ClientError {
+    #[error("No Advent of Code session token found")]
+    #[diagnostic(help(
+        "set AOC_SESSION, or save the `session` cookie from the website to ~/{SESSION_FILE}"
+    ))]
+    MissingSession,
+    #[error("{year} day {day} is not unlocked yet")]
+    #[diagnostic(help("puzzles unlock at midnight EST (UTC-5)"))]
+    NotUnlocked { year: u16, day: u8 },
+    #[error("The session token was rejected (HTTP {status})")]
+    #[diagnostic(help("log in again and copy a fresh `session` cookie"))]
+    BadSession { status: u16 },
+    #[error("Unexpected response from the server (HTTP {status})")]
+    Http { status: u16 },
+    #[error("Request failed")]
+    Transport {
+        #[source]
+        source: Box<ureq::Error>,
+    },
+    #[error("Failed to read the response")]
+    Body {
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Input already downloaded to {0}")]
+    #[diagnostic(help("delete the file to download it again"))]
+    AlreadyCached(PathBuf),
+    #[error("Day directory {0} does not exist")]
+    #[diagnostic(help("create the day crate before downloading its input"))]
+    MissingDayDir(PathBuf),
+    #[error("Failed to write {path}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Blocking client for the Advent of Code website, authenticated with a session cookie.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            interval: DEFAULT_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    /// Override the minimum gap between requests.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Fetch the raw puzzle input for a day.
+    #[tracing::instrument(skip(self))]
+    pub fn input(&mut self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        self.throttle();
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        read_body(response, year, day)
+    }
+
+    /// Post an answer for one part, returning the raw HTML of the response page.
+    #[tracing::instrument(skip(self))]
+    pub fn submit(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        self.throttle();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        read_body(response, year, day)
+    }
+
+    fn throttle(&mut self) {
+        if let Some(last) = self.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                tracing::debug!(
+                    "Waiting {:?} before the next request",
+                    self.interval - elapsed
+                );
+                std::thread::sleep(self.interval - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+/// Find the session token, preferring an explicit value over `~/.adventofcode.session`.
+pub fn session(explicit: Option<String>) -> Result<String, ClientError> {
+    let token = explicit.or_else(|| {
+        let home = std::env::var_os("HOME")?;
+        std::fs::read_to_string(Path::new(&home).join(SESSION_FILE)).ok()
+    });
+    token
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or(ClientError::MissingSession)
+}
+
+/// Download a day's input into `dir/input.txt`, refusing to replace one that already exists.
+pub fn download_input(
+    client: &mut Client,
+    year: u16,
+    day: u8,
+    dir: &Path,
+) -> Result<PathBuf, ClientError> {
+    let path = dir.join("input.txt");
+    if path.exists() {
+        return Err(ClientError::AlreadyCached(path));
+    }
+    if !dir.is_dir() {
+        return Err(ClientError::MissingDayDir(dir.to_path_buf()));
+    }
+    let input = client.input(year, day)?;
+    std::fs::write(&path, input).map_err(|source| ClientError::Write {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(path)
+}
+
+fn read_body(
+    response: Result<ureq::Response, ureq::Error>,
+    year: u16,
+    day: u8,
+) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|source| ClientError::Body { source }),
+        // NOTE: The site answers 400 for a malformed cookie and 500 for an expired one
+        Err(ureq::Error::Status(404, _)) => Err(ClientError::NotUnlocked { year, day }),
+        Err(ureq::Error::Status(status @ (400 | 401 | 403 | 500), _)) => {
+            Err(ClientError::BadSession { status })
+        }
+        Err(ureq::Error::Status(status, _)) => Err(ClientError::Http { status }),
+        Err(source @ ureq::Error::Transport(_)) => Err(ClientError::Transport {
+            source: Box::new(source),
+        }),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::thread::JoinHandle;
+    use test_log::test;
+
+    /// A request as seen by the mock server.
+    #[derive(Debug)]
+    pub(crate) struct Seen {
+        pub url: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Serve the canned responses in order on a local port, then stop.
+    pub(crate) fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Seen>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("bind mock server");
+        let base_url = format!("http://{}", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            let mut seen = vec![];
+            for (status, page) in responses {
+                let mut request = server.recv().expect("receive request");
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                let mut body = String::new();
+                request
+                    .as_reader()
+                    .read_to_string(&mut body)
+                    .expect("read request body");
+                seen.push(Seen {
+                    url: request.url().to_string(),
+                    cookie,
+                    body,
+                });
+                request
+                    .respond(tiny_http::Response::from_string(page).with_status_code(status))
+                    .expect("send response");
+            }
+            seen
+        });
+        (base_url, handle)
+    }
+
+    /// Empty scratch directory unique to one test.
+    pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create scratch dir");
+        dir
+    }
+
+    #[test]
+    fn test_download_writes_input() -> miette::Result<()> {
+        let (base_url, server) = serve(vec![(200, "3   4\n4   3\n")]);
+        let dir = scratch_dir("download");
+        let mut client = Client::new(&base_url, "abc\n");
+
+        let path = download_input(&mut client, 2024, 6, &dir)?;
+
+        assert_eq!(
+            "3   4\n4   3\n",
+            std::fs::read_to_string(path).expect("input written")
+        );
+        let seen = server.join().expect("mock server");
+        assert_eq!("/2024/day/6/input", seen[0].url);
+        assert_eq!(Some("session=abc".to_string()), seen[0].cookie);
+        Ok(())
+    }
+
+    #[test]
+    fn test_refuses_cached_input() {
+        let dir = scratch_dir("cached");
+        std::fs::write(dir.join("input.txt"), "cached").expect("seed input");
+        // NOTE: Nothing listens on this port, so any request would fail with a transport error
+        let mut client = Client::new("http://127.0.0.1:9", "abc");
+
+        let result = download_input(&mut client, 2024, 6, &dir);
+
+        assert!(matches!(result, Err(ClientError::AlreadyCached(_))));
+    }
+
+    #[test]
+    fn test_status_errors() {
+        let (base_url, server) = serve(vec![(404, ""), (400, ""), (500, ""), (502, "")]);
+        let mut client = Client::new(&base_url, "abc").with_interval(Duration::ZERO);
+
+        assert!(matches!(
+            client.input(2024, 25),
+            Err(ClientError::NotUnlocked {
+                year: 2024,
+                day: 25
+            })
+        ));
+        assert!(matches!(
+            client.input(2024, 1),
+            Err(ClientError::BadSession { status: 400 })
+        ));
+        assert!(matches!(
+            client.input(2024, 1),
+            Err(ClientError::BadSession { status: 500 })
+        ));
+        assert!(matches!(
+            client.input(2024, 1),
+            Err(ClientError::Http { status: 502 })
+        ));
+        server.join().expect("mock server");
+    }
+
+    #[test]
+    fn test_transport_errors_keep_their_source() {
+        // NOTE: Nothing listens on a port once its listener is dropped, so connecting fails
+        let address = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("bind unused port");
+        let mut client = Client::new(&format!("http://{address}"), "abc");
+
+        let error = client.input(2024, 1).unwrap_err();
+
+        assert!(matches!(error, ClientError::Transport { .. }), "{error:?}");
+        let source = std::error::Error::source(&error).expect("ureq error as source");
+        assert!(source.to_string().contains(&address.to_string()), "{source}");
+    }
+
+    #[test]
+    fn test_requests_are_throttled() -> miette::Result<()> {
+        let (base_url, server) = serve(vec![(200, "a"), (200, "b")]);
+        let mut client = Client::new(&base_url, "abc").with_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.input(2024, 1)?;
+        client.input(2024, 2)?;
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        server.join().expect("mock server");
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_prefers_explicit_token() {
+        assert_eq!(
+            "abc",
+            session(Some(" abc\n".to_string())).expect("explicit token")
+        );
+        assert!(matches!(
+            session(Some("  ".to_string())),
+            Err(ClientError::MissingSession)
+        ));
+    }
+}
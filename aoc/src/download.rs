@@ -0,0 +1,20 @@
+use crate::client::{self, Client, ClientError};
+use crate::registry;
+
+/// Download one day's input, or every registered day of the year that doesn't have one yet.
+pub fn download(mut client: Client, year: u16, day: Option<u8>) -> miette::Result<()> {
+    if let Some(day) = day {
+        let path = client::download_input(&mut client, year, day, &registry::day_dir(year, day))?;
+        println!("Downloaded {}", path.display());
+        return Ok(());
+    }
+
+    for entry in registry::select(Some(year), None) {
+        match client::download_input(&mut client, entry.year, entry.day, &entry.dir()) {
+            Ok(path) => println!("Downloaded {}", path.display()),
+            Err(ClientError::AlreadyCached(_)) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
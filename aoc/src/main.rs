@@ -0,0 +1,222 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_common::solution::Part;
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod client;
+mod download;
+mod new;
+mod registry;
+mod run;
+mod submit;
+mod verify;
+
+#[derive(Parser, Debug)]
+#[command(about = "Run Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one or more days, printing each answer with its timing
+    Run {
+        /// Only run days from this year
+        #[arg(long)]
+        year: Option<u16>,
+        /// Only run this day, every registered day is run when omitted
+        #[arg(long)]
+        day: Option<u8>,
+        /// Only run this part, both parts are run when omitted
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Read the puzzle input from this file, or `-` for stdin, instead of the day's input.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Record the answers of parts with no answer yet in the day's answers.toml, for puzzles
+        /// solved before answers were tracked. Recorded answers are never overwritten
+        #[arg(long, conflicts_with = "input")]
+        save: bool,
+        /// Also print how each answer comes about, for days that explain it
+        #[arg(long)]
+        breakdown: bool,
+    },
+    /// Download puzzle inputs into each day's input.txt, skipping ones already present
+    Download {
+        #[arg(long)]
+        year: u16,
+        /// Only download this day, every registered day without an input is fetched when omitted
+        #[arg(long)]
+        day: Option<u8>,
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Create a day crate from daily-template and register it with the runner
+    New {
+        #[arg(long)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        /// Also download the day's input
+        #[arg(long)]
+        download: bool,
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Solve one part and submit the answer, recording the site's verdict in answers.toml
+    Submit {
+        #[arg(long)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        #[arg(long, value_parser = parse_part)]
+        part: Part,
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Record divan benchmark results and report changes between runs
+    Bench {
+        #[command(subcommand)]
+        action: BenchAction,
+        /// Percentage slowdown in the median time that counts as a regression, which makes the
+        /// command fail
+        #[arg(long, global = true, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Re-run every registered part against the recorded answers
+    Verify {
+        /// Only verify days from this year
+        #[arg(long)]
+        year: Option<u16>,
+        /// Only verify this day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchAction {
+    /// Run `cargo bench` and append the results to bench-history.json
+    Record {
+        /// Only bench this package, such as day-2024-06
+        #[arg(long, short)]
+        package: Option<String>,
+        /// Only run benchmarks whose name matches this filter, such as part2
+        #[arg(long)]
+        filter: Option<String>,
+        /// Name this run so later reports can compare against it
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Parse divan output saved in this file instead of running the benches
+        #[arg(long)]
+        from: Option<PathBuf>,
+        /// Commit the saved output was measured at, the current one when omitted
+        #[arg(long, requires = "from")]
+        commit: Option<String>,
+    },
+    /// Print the latest run as a markdown table
+    Report {
+        /// Compare against the latest run recorded with this baseline name instead of the
+        /// previous run
+        #[arg(long)]
+        baseline: Option<String>,
+    },
+}
+
+/// Connection settings for the Advent of Code website.
+#[derive(clap::Args, Debug)]
+struct Site {
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Session cookie, read from ~/.adventofcode.session when not set
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+    /// Minimum number of seconds between requests
+    #[arg(long, default_value_t = client::DEFAULT_INTERVAL.as_secs())]
+    throttle: u64,
+}
+
+impl Site {
+    fn client(self) -> miette::Result<client::Client> {
+        let session = client::session(self.session)?;
+        Ok(client::Client::new(&self.base_url, &session)
+            .with_interval(Duration::from_secs(self.throttle)))
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    let number: u8 = value.parse().map_err(|e| format!("{e}"))?;
+    Part::try_from(number).map_err(|e| e.to_string())
+}
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    match Cli::parse().command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            save,
+            breakdown,
+        } => run::run(run::Options {
+            year,
+            day,
+            part,
+            input,
+            save,
+            breakdown,
+        }),
+        Command::Download { year, day, site } => download::download(site.client()?, year, day),
+        Command::New {
+            year,
+            day,
+            download,
+            site,
+        } => {
+            let dir = new::scaffold(registry::workspace_root(), year, day)?;
+            if download {
+                match client::download_input(&mut site.client()?, year, day, &dir) {
+                    Ok(path) => println!("Downloaded {}", path.display()),
+                    Err(client::ClientError::AlreadyCached(_)) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Ok(())
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            site,
+        } => submit::submit(site.client()?, year, day, part),
+        Command::Bench { action, threshold } => match action {
+            BenchAction::Record {
+                package,
+                filter,
+                baseline,
+                from,
+                commit,
+            } => bench::record(
+                bench::RecordOptions {
+                    package,
+                    filter,
+                    baseline,
+                    from,
+                    commit,
+                },
+                threshold,
+            ),
+            BenchAction::Report { baseline } => bench::report(
+                baseline.map_or(bench::Reference::Previous, bench::Reference::Baseline),
+                threshold,
+            ),
+        },
+        Command::Verify { year, day } => verify::verify(year, day),
+    }
+}
@@ -0,0 +1,282 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::answers::ANSWERS_FILE;
+use miette::{Diagnostic, IntoDiagnostic, WrapErr};
+use thiserror::Error;
+
+/// Marker line in the runner's `Cargo.toml` and `registry.rs` that new days are inserted above
+/// when no registered day sorts after them.
+const MARKER: &str = "`aoc new` adds days above this line";
+
+/// Files of `daily-template`, relative to the new crate.
+const TEMPLATE: [(&str, &str); 7] = [
+    (
+        "Cargo.toml",
+        include_str!("../../daily-template/Cargo.toml"),
+    ),
+    (
+        "src/lib.rs",
+        include_str!("../../daily-template/src/lib.rs"),
+    ),
+    (
+        "src/part1.rs",
+        include_str!("../../daily-template/src/part1.rs"),
+    ),
+    (
+        "src/part2.rs",
+        include_str!("../../daily-template/src/part2.rs"),
+    ),
+    (
+        "src/bin/part1.rs",
+        include_str!("../../daily-template/src/bin/part1.rs"),
+    ),
+    (
+        "src/bin/part2.rs",
+        include_str!("../../daily-template/src/bin/part2.rs"),
+    ),
+    (
+        "benches/benchmark.rs",
+        include_str!("../../daily-template/benches/benchmark.rs"),
+    ),
+];
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum NewError {
+    #[error("Day must be between 1 and 25, got {0}")]
+    InvalidDay(u8),
+    #[error("Could not find the `{MARKER}` marker in {0}")]
+    #[diagnostic(help("add the marker comment back where new days should be inserted"))]
+    MissingMarker(PathBuf),
+}
+
+/// Names used when rendering the template for one day.
+#[derive(Debug, PartialEq, Eq)]
+struct Names {
+    /// Crate directory relative to the workspace root, `2024/day-06`.
+    dir: String,
+    /// Package name, `day-2024-06`, unique across years.
+    package: String,
+    /// Rust crate name, `day_2024_06`.
+    crate_name: String,
+    /// Solution type, `Day06`.
+    solution: String,
+}
+
+impl Names {
+    fn new(year: u16, day: u8) -> Self {
+        Names {
+            dir: format!("{year}/day-{day:02}"),
+            package: format!("day-{year}-{day:02}"),
+            crate_name: format!("day_{year}_{day:02}"),
+            solution: format!("Day{day:02}"),
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{project-name | upper_camel_case}}", &self.solution)
+            .replace("{{project-name}}", &self.package)
+            .replace("{{crate_name}}", &self.crate_name)
+    }
+}
+
+/// Create a day crate in the workspace at `root` and register it with the runner. Existing
+/// files are never overwritten, so running this again only fills in whatever is missing.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> miette::Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(NewError::InvalidDay(day).into());
+    }
+    let names = Names::new(year, day);
+    let dir = root.join(&names.dir);
+
+    if dir.exists() {
+        println!("{} already exists, leaving its files alone", dir.display());
+    } else {
+        for (path, template) in TEMPLATE {
+            write_new(&dir.join(path), &names.render(template))?;
+        }
+        println!("Created {}", dir.display());
+    }
+    for path in ["example.txt", ANSWERS_FILE] {
+        if !dir.join(path).exists() {
+            write_new(&dir.join(path), "")?;
+        }
+    }
+
+    let manifest = root.join("aoc/Cargo.toml");
+    let dependency = format!("{} = {{ path = \"../{}\" }}", names.package, names.dir);
+    insert_in_order(&manifest, &dependency, (year, day), dependency_key)?;
+
+    let registry = root.join("aoc/src/registry.rs");
+    let entry = format!(
+        "    {year}, {day}, \"{}\" => {}::{};",
+        names.dir, names.crate_name, names.solution
+    );
+    insert_in_order(&registry, &entry, (year, day), registry_key)?;
+
+    Ok(dir)
+}
+
+fn write_new(path: &Path, contents: &str) -> miette::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("create {}", parent.display()))?;
+    }
+    std::fs::write(path, contents)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("write {}", path.display()))
+}
+
+/// `(year, day)` of a `day-2024-06 = { path = ... }` dependency line.
+fn dependency_key(line: &str) -> Option<(u16, u8)> {
+    let (name, _) = line.split_once(" = ")?;
+    let (year, day) = name.trim().strip_prefix("day-")?.split_once('-')?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// `(year, day)` of a `2024, 6, "2024/day-06" => ...;` registry line.
+fn registry_key(line: &str) -> Option<(u16, u8)> {
+    let mut fields = line.trim().splitn(3, ", ");
+    let year = fields.next()?.parse().ok()?;
+    let day = fields.next()?.parse().ok()?;
+    fields.next()?;
+    Some((year, day))
+}
+
+/// Add `line` to `path` before the first day that sorts after `key`, or above the marker if
+/// there is none, unless the file already contains it.
+fn insert_in_order(
+    path: &Path,
+    line: &str,
+    key: (u16, u8),
+    key_of: fn(&str) -> Option<(u16, u8)>,
+) -> miette::Result<()> {
+    let text = std::fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("read {}", path.display()))?;
+    if text.lines().any(|existing| existing.trim() == line.trim()) {
+        return Ok(());
+    }
+    let marker = text
+        .lines()
+        .position(|existing| existing.contains(MARKER))
+        .ok_or_else(|| NewError::MissingMarker(path.to_path_buf()))?;
+    let position = text
+        .lines()
+        .take(marker)
+        .position(|existing| key_of(existing).is_some_and(|existing| existing > key))
+        .unwrap_or(marker);
+
+    let mut lines: Vec<&str> = text.lines().collect();
+    lines.insert(position, line);
+    std::fs::write(path, lines.join("\n") + "\n")
+        .into_diagnostic()
+        .wrap_err_with(|| format!("write {}", path.display()))?;
+    println!("Registered in {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::scratch_dir;
+    use test_log::test;
+
+    /// Workspace with just enough of the runner for `scaffold` to edit.
+    fn workspace(name: &str) -> PathBuf {
+        let root = scratch_dir(name);
+        std::fs::create_dir_all(root.join("aoc/src")).expect("create runner dir");
+        std::fs::write(
+            root.join("aoc/Cargo.toml"),
+            format!("[dependencies]\nday-2024-01 = {{ path = \"../2024/day-01\" }}\n# {MARKER}\n"),
+        )
+        .expect("write manifest");
+        std::fs::write(
+            root.join("aoc/src/registry.rs"),
+            format!(
+                "register! {{\n    2024, 1, \"2024/day-01\" => day_2024_01::Day01;\n    // {MARKER}\n}}\n"
+            ),
+        )
+        .expect("write registry");
+        root
+    }
+
+    #[test]
+    fn test_render_names() {
+        let names = Names::new(2024, 7);
+        assert_eq!(
+            "use day_2024_07::Day07; // day-2024-07",
+            names.render(
+                "use {{crate_name}}::{{project-name | upper_camel_case}}; // {{project-name}}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_scaffold_creates_and_registers_day() -> miette::Result<()> {
+        let root = workspace("new");
+
+        let dir = scaffold(&root, 2024, 12)?;
+
+        let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).into_diagnostic()?;
+        assert!(dir.ends_with("2024/day-12"));
+        assert!(manifest.contains("name = \"day-2024-12\""));
+        assert!(manifest.contains("name = \"day-2024-12-bench\""));
+        assert!(manifest.contains("name = \"day-2024-12-part2\""));
+        assert!(dir.join("src/bin/part1.rs").exists());
+        let lib = std::fs::read_to_string(dir.join("src/lib.rs")).into_diagnostic()?;
+        assert!(lib.contains("impl Solution for Day12"));
+        assert!(!lib.contains("{{"));
+        assert_eq!(
+            "",
+            std::fs::read_to_string(dir.join("example.txt")).into_diagnostic()?
+        );
+        assert!(dir.join(ANSWERS_FILE).exists());
+
+        let registry =
+            std::fs::read_to_string(root.join("aoc/src/registry.rs")).into_diagnostic()?;
+        assert!(registry.contains(
+            "    2024, 12, \"2024/day-12\" => day_2024_12::Day12;\n    // `aoc new` adds days above this line"
+        ));
+        let runner = std::fs::read_to_string(root.join("aoc/Cargo.toml")).into_diagnostic()?;
+        assert!(runner.contains("day-2024-12 = { path = \"../2024/day-12\" }\n# `aoc new`"));
+
+        scaffold(&root, 2023, 2)?;
+
+        let registry =
+            std::fs::read_to_string(root.join("aoc/src/registry.rs")).into_diagnostic()?;
+        let keys: Vec<_> = registry.lines().filter_map(registry_key).collect();
+        assert_eq!(vec![(2023, 2), (2024, 1), (2024, 12)], keys);
+        let runner = std::fs::read_to_string(root.join("aoc/Cargo.toml")).into_diagnostic()?;
+        let keys: Vec<_> = runner.lines().filter_map(dependency_key).collect();
+        assert_eq!(vec![(2023, 2), (2024, 1), (2024, 12)], keys);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaffold_is_idempotent() -> miette::Result<()> {
+        let root = workspace("new-again");
+        let dir = scaffold(&root, 2024, 3)?;
+        std::fs::write(dir.join("src/part1.rs"), "// work in progress").into_diagnostic()?;
+        let registry_before =
+            std::fs::read_to_string(root.join("aoc/src/registry.rs")).into_diagnostic()?;
+
+        scaffold(&root, 2024, 3)?;
+
+        assert_eq!(
+            "// work in progress",
+            std::fs::read_to_string(dir.join("src/part1.rs")).into_diagnostic()?
+        );
+        assert_eq!(
+            registry_before,
+            std::fs::read_to_string(root.join("aoc/src/registry.rs")).into_diagnostic()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaffold_rejects_invalid_day() {
+        assert!(scaffold(&workspace("new-invalid"), 2024, 26).is_err());
+    }
+}
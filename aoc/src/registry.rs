@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::answers::AnswerStore;
+use aoc_common::solution::{Part, Solution, Solved};
+
+/// A day crate the runner knows how to solve.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// Crate directory, relative to the workspace root.
+    pub dir: &'static str,
+    solve_parts: fn(&str, &[Part], bool) -> miette::Result<Vec<Solved>>,
+}
+
+impl Day {
+    /// Parse `input` once and solve each of `parts`, see [`Solution::solve_parts`].
+    pub fn solve_parts(
+        &self,
+        input: &str,
+        parts: &[Part],
+        breakdown: bool,
+    ) -> miette::Result<Vec<Solved>> {
+        (self.solve_parts)(input, parts, breakdown)
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        workspace_root().join(self.dir)
+    }
+
+    /// Where the puzzle input lives when no `--input` is given.
+    pub fn input_path(&self) -> PathBuf {
+        self.dir().join("input.txt")
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        AnswerStore::beside(&self.input_path())
+    }
+}
+
+/// Root of the workspace this runner was built in, so inputs resolve from any directory.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner crate lives inside the workspace")
+}
+
+macro_rules! register {
+    ($($year:literal, $day:literal, $dir:literal => $solution:ty;)*) => {
+        /// Every registered day, ordered by year then day.
+        pub static DAYS: &[Day] = &[
+            $(Day {
+                year: $year,
+                day: $day,
+                dir: $dir,
+                solve_parts: <$solution as Solution>::solve_parts,
+            },)*
+        ];
+    };
+}
+
+register! {
+    2023, 1, "2023/day-01" => day_2023_01::Day01;
+    2024, 1, "2024/day-01" => day_2024_01::Day01;
+    2024, 2, "2024/day-02" => day_2024_02::Day02;
+    2024, 3, "2024/day-03" => day_2024_03::Day03;
+    2024, 4, "2024/day-04" => day_2024_04::Day04;
+    2024, 5, "2024/day-05" => day_2024_05::Day05;
+    2024, 6, "2024/day-06" => day_2024_06::Day06;
+    2024, 7, "2024/day-07" => day_2024_07::Day07;
+    2024, 8, "2024/day-08" => day_2024_08::Day08;
+    2024, 9, "2024/day-09" => day_2024_09::Day09;
+    2024, 10, "2024/day-10" => day_2024_10::Day10;
+    2024, 11, "2024/day-11" => day_2024_11::Day11;
+    // `aoc new` adds days above this line
+}
+
+/// Directory holding a year's day crates.
+pub fn year_dir(year: u16) -> PathBuf {
+    workspace_root().join(year.to_string())
+}
+
+/// Crate directory for a day, including days that haven't been registered yet.
+pub fn day_dir(year: u16, day: u8) -> PathBuf {
+    match find(year, day) {
+        Some(entry) => entry.dir(),
+        None => year_dir(year).join(format!("day-{day:02}")),
+    }
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+/// Registered days matching the optional year and day filters.
+pub fn select(year: Option<u16>, day: Option<u8>) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |entry| {
+        year.is_none_or(|year| entry.year == year) && day.is_none_or(|day| entry.day == day)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::solution::Answer;
+    use test_log::test;
+
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        for pair in DAYS.windows(2) {
+            assert!((pair[0].year, pair[0].day) < (pair[1].year, pair[1].day));
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let day = find(2024, 6).expect("day 6 is registered");
+        assert_eq!("2024/day-06", day.dir);
+        assert!(day.input_path().ends_with("2024/day-06/input.txt"));
+        assert!(find(2024, 26).is_none());
+        assert!(find(2023, 1)
+            .expect("2023 day 1 is registered")
+            .input_path()
+            .ends_with("2023/day-01/input.txt"));
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(DAYS.len(), select(None, None).count());
+        assert_eq!(
+            DAYS.len(),
+            select(Some(2023), None).count() + select(Some(2024), None).count()
+        );
+        assert_eq!(1, select(Some(2023), Some(1)).count());
+        assert_eq!(1, select(Some(2024), Some(11)).count());
+        assert_eq!(0, select(Some(2015), None).count());
+    }
+
+    #[test]
+    fn test_solve_through_registry() -> miette::Result<()> {
+        let day = find(2024, 1).expect("day 1 is registered");
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        let answers: Vec<Answer> = day
+            .solve_parts(input, &Part::BOTH, false)?
+            .into_iter()
+            .map(|solved| solved.answer)
+            .collect::<miette::Result<_>>()?;
+        assert_eq!(vec![Answer::Integer(11), Answer::Integer(31)], answers);
+        Ok(())
+    }
+}
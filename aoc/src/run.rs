@@ -0,0 +1,133 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+use aoc_common::answers::AnswerStore;
+use aoc_common::solution::{Part, Solved};
+use miette::{Diagnostic, IntoDiagnostic, WrapErr};
+use thiserror::Error;
+
+use crate::registry::{self, Day};
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum RunError {
+    #[error("No solution registered for {year} day {day}")]
+    UnknownDay { year: u16, day: u8 },
+    #[error("No days registered matching the given filters")]
+    NothingSelected,
+    #[error("--input can only be used when running a single day")]
+    #[diagnostic(help("pass both --year and --day"))]
+    InputNeedsSingleDay,
+    #[error("No puzzle input at {0}")]
+    #[diagnostic(help("download it, or pass --input <path>"))]
+    MissingInput(PathBuf),
+}
+
+pub struct Options {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub input: Option<PathBuf>,
+    pub save: bool,
+    pub breakdown: bool,
+}
+
+pub fn run(options: Options) -> miette::Result<()> {
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    if let (Some(year), Some(day)) = (options.year, options.day) {
+        let entry = registry::find(year, day).ok_or(RunError::UnknownDay { year, day })?;
+        let input = match &options.input {
+            Some(path) => read_input(path.clone())?,
+            None => read_input(entry.input_path())?,
+        };
+        return solve(entry, &input, &parts, &options);
+    }
+
+    if options.input.is_some() {
+        return Err(RunError::InputNeedsSingleDay.into());
+    }
+    let days: Vec<&Day> = registry::select(options.year, options.day).collect();
+    if days.is_empty() {
+        return Err(RunError::NothingSelected.into());
+    }
+    for entry in days {
+        let path = entry.input_path();
+        if !path.exists() {
+            // NOTE: Running everything shouldn't stop at the first day nobody downloaded yet
+            println!(
+                "{} day {:02}: skipped, no input at {}",
+                entry.year,
+                entry.day,
+                path.display()
+            );
+            continue;
+        }
+        solve(entry, &read_input(path)?, &parts, &options)?;
+    }
+    Ok(())
+}
+
+/// Read puzzle input from a file, or from stdin when the path is `-`.
+pub fn read_input(path: PathBuf) -> miette::Result<String> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .into_diagnostic()
+            .wrap_err("read input from stdin")?;
+        return Ok(input);
+    }
+    if !path.exists() {
+        return Err(RunError::MissingInput(path).into());
+    }
+    std::fs::read_to_string(&path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("read input file {}", path.display()))
+}
+
+fn solve(entry: &Day, input: &str, parts: &[Part], options: &Options) -> miette::Result<()> {
+    let solved = entry
+        .solve_parts(input, parts, options.breakdown)
+        .wrap_err_with(|| format!("{} day {:02}", entry.year, entry.day))?;
+    let mut answers = vec![];
+    for Solved {
+        part,
+        answer,
+        elapsed,
+        breakdown,
+    } in solved
+    {
+        let label = || format!("{} day {:02} part {}", entry.year, entry.day, part);
+        let answer = answer.wrap_err_with(label)?;
+        println!("{}: {} ({:.2?})", label(), answer, elapsed);
+        for line in breakdown.wrap_err_with(label)? {
+            println!("  {line}");
+        }
+        answers.push((part, answer));
+    }
+
+    if options.save {
+        let path = entry.answers_path();
+        let mut store = AnswerStore::load(&path)?;
+        let mut saved = false;
+        for (part, answer) in &answers {
+            // NOTE: Once a part has an answer it only changes through `aoc submit`, so a
+            // regression can't overwrite the one the site accepted
+            match store.answer(*part) {
+                Some(recorded) => println!("Part {part} already has {recorded}, not saving"),
+                None => {
+                    store.set_answer(*part, answer);
+                    saved = true;
+                }
+            }
+        }
+        if saved {
+            store.save(&path)?;
+            println!("Saved answers to {}", path.display());
+        }
+    }
+    Ok(())
+}
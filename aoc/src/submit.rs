@@ -0,0 +1,271 @@
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_common::answers::{AnswerStore, Rejection};
+use aoc_common::solution::{Answer, Part};
+use miette::{Diagnostic, WrapErr};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::registry::{self, Day};
+use crate::run::{read_input, RunError};
+
+/// What the site said about a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "answered too recently, wait {wait:?}"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum SubmitError {
+    #[error("Not submitting {answer}")]
+    #[diagnostic(help("the recorded feedback in answers.toml already rules this answer out"))]
+    Rejected {
+        answer: Answer,
+        #[source]
+        reason: Rejection,
+    },
+    #[error("Could not understand the response to the submission")]
+    #[diagnostic(help("the page said: {0}"))]
+    UnknownResponse(String),
+    #[error("{answer} was not accepted: {outcome}")]
+    NotAccepted { answer: Answer, outcome: Outcome },
+}
+
+/// Solve a part against the day's input and submit the answer.
+pub fn submit(mut client: Client, year: u16, day: u8, part: Part) -> miette::Result<()> {
+    let entry: &Day = registry::find(year, day).ok_or(RunError::UnknownDay { year, day })?;
+    let input = read_input(entry.input_path())?;
+    let answer = entry
+        .solve_parts(&input, &[part], false)
+        .and_then(|mut solved| solved.remove(0).answer)
+        .wrap_err_with(|| format!("{year} day {day:02} part {part}"))?;
+    println!("{year} day {day:02} part {part}: submitting {answer}");
+
+    let outcome = submit_answer(&mut client, year, day, part, &answer, &entry.answers_path())?;
+    match outcome {
+        Outcome::Correct | Outcome::AlreadySolved => {
+            println!("{answer} is {outcome}");
+            Ok(())
+        }
+        outcome => Err(SubmitError::NotAccepted { answer, outcome }.into()),
+    }
+}
+
+/// Submit an answer unless the store proves it wrong, then record what the site said.
+pub fn submit_answer(
+    client: &mut Client,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+    answers_path: &Path,
+) -> miette::Result<Outcome> {
+    let mut store = AnswerStore::load(answers_path)?;
+    if let Some(reason) = store.reject(part, answer) {
+        return Err(SubmitError::Rejected {
+            answer: answer.clone(),
+            reason,
+        }
+        .into());
+    }
+
+    let page = client.submit(year, day, part, &answer.to_string())?;
+    let outcome = parse_outcome(&page)?;
+    tracing::debug!("Submission outcome: {:?}", outcome);
+
+    match outcome {
+        Outcome::Correct => store.set_answer(part, answer),
+        Outcome::TooHigh => store.record_too_high(part, answer),
+        Outcome::TooLow => store.record_too_low(part, answer),
+        Outcome::Wrong => store.record_wrong(part, answer),
+        Outcome::RateLimited(_) | Outcome::AlreadySolved => return Ok(outcome),
+    }
+    store.save(answers_path)?;
+    Ok(outcome)
+}
+
+/// Classify the `<article>` message on the page returned after submitting.
+pub fn parse_outcome(page: &str) -> Result<Outcome, SubmitError> {
+    let message = article_text(page);
+    let outcome = if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if message.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if message.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&message).unwrap_or(Duration::from_secs(60)))
+    } else if message.contains("Did you already complete it") {
+        Outcome::AlreadySolved
+    } else {
+        return Err(SubmitError::UnknownResponse(message));
+    };
+    Ok(outcome)
+}
+
+/// Text of the page's `<article>` element with the markup stripped.
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in article.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            ch if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse the wait from "You have 1m 5s left to wait", or `None` if it isn't in that form.
+fn parse_wait(message: &str) -> Option<Duration> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = message[start..].find(" left to wait")? + start;
+    let seconds = message[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (split, unit) = amount.char_indices().next_back()?;
+            let value: u64 = amount[..split].parse().ok()?;
+            match unit {
+                'h' => value.checked_mul(3600),
+                'm' => value.checked_mul(60),
+                's' => Some(value),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<u64>>>()?;
+    if seconds.is_empty() {
+        return None;
+    }
+    seconds
+        .into_iter()
+        .try_fold(0u64, u64::checked_add)
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{scratch_dir, serve};
+    use test_log::test;
+
+    const CORRECT: &str = r#"<main><article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to finding the Chief Historian.</p></article></main>"#;
+    const TOO_HIGH: &str = r#"<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href="/2024/day/6">[Return to Day 6]</a></p></article></main>"#;
+    const TOO_LOW: &str = r#"<main><article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article></main>"#;
+    const WRONG: &str = r#"<main><article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article></main>"#;
+    const RATE_LIMITED: &str = r#"<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href="/2024/day/6">[Return to Day 6]</a></p></article></main>"#;
+    const ALREADY_SOLVED: &str = r#"<main><article><p>You don't seem to be solving the right level.  Did you already complete it? <a href="/2024/day/6">[Return to Day 6]</a></p></article></main>"#;
+
+    #[test]
+    fn test_parse_outcome() -> miette::Result<()> {
+        assert_eq!(Outcome::Correct, parse_outcome(CORRECT)?);
+        assert_eq!(Outcome::TooHigh, parse_outcome(TOO_HIGH)?);
+        assert_eq!(Outcome::TooLow, parse_outcome(TOO_LOW)?);
+        assert_eq!(Outcome::Wrong, parse_outcome(WRONG)?);
+        assert_eq!(
+            Outcome::RateLimited(Duration::from_secs(65)),
+            parse_outcome(RATE_LIMITED)?
+        );
+        assert_eq!(Outcome::AlreadySolved, parse_outcome(ALREADY_SOLVED)?);
+        assert!(matches!(
+            parse_outcome("<article><p>Something else</p></article>"),
+            Err(SubmitError::UnknownResponse(message)) if message == "Something else"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_wait_rejects_odd_amounts() {
+        let wait = |amounts| parse_wait(&format!("You have {amounts} left to wait."));
+        assert_eq!(Some(Duration::from_secs(3725)), wait("1h 2m 5s"));
+        assert_eq!(None, wait("5é"));
+        assert_eq!(None, wait("m"));
+        assert_eq!(None, wait("5x"));
+        assert_eq!(None, wait(&format!("{}h", u64::MAX)));
+        assert_eq!(None, parse_wait("You have  left to wait"));
+    }
+
+    #[test]
+    fn test_submit_records_feedback() -> miette::Result<()> {
+        let (base_url, server) = serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let path = scratch_dir("submit").join("answers.toml");
+        let mut client = Client::new(&base_url, "abc").with_interval(Duration::ZERO);
+
+        let outcome = submit_answer(
+            &mut client,
+            2024,
+            6,
+            Part::Two,
+            &Answer::from(100u32),
+            &path,
+        )?;
+        assert_eq!(Outcome::TooHigh, outcome);
+
+        // NOTE: The server only has two responses, so this must be rejected without a request
+        let rejected = submit_answer(
+            &mut client,
+            2024,
+            6,
+            Part::Two,
+            &Answer::from(150u32),
+            &path,
+        );
+        assert!(rejected.is_err());
+
+        let outcome = submit_answer(&mut client, 2024, 6, Part::Two, &Answer::from(42u32), &path)?;
+        assert_eq!(Outcome::Correct, outcome);
+
+        let store = AnswerStore::load(&path)?;
+        assert_eq!(Some("42"), store.answer(Part::Two));
+        assert_eq!(
+            Some(Rejection::AlreadyAccepted("42".to_string())),
+            store.reject(Part::Two, &Answer::from(7u32))
+        );
+
+        let seen = server.join().expect("mock server");
+        assert_eq!("/2024/day/6/answer", seen[0].url);
+        assert_eq!("level=2&answer=100", seen[0].body);
+        assert_eq!("level=2&answer=42", seen[1].body);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_limited_records_nothing() -> miette::Result<()> {
+        let (base_url, server) = serve(vec![(200, RATE_LIMITED)]);
+        let path = scratch_dir("rate-limited").join("answers.toml");
+        let mut client = Client::new(&base_url, "abc");
+
+        let outcome = submit_answer(&mut client, 2024, 6, Part::One, &Answer::from(5u32), &path)?;
+
+        assert_eq!(Outcome::RateLimited(Duration::from_secs(65)), outcome);
+        assert!(!path.exists());
+        server.join().expect("mock server");
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use std::path::Path;
+
+use aoc_common::answers::{verify_parts, Verdict};
+use aoc_common::solution::Part;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::registry::{self, Day};
+
+#[derive(Error, Diagnostic, Debug)]
+#[error("{0} part(s) failed to solve or no longer match their recorded answers")]
+pub struct VerifyFailed(usize);
+
+/// Status of one registered part, as shown in the verify table.
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// No input downloaded, or no answer recorded yet.
+    Missing,
+}
+
+struct Row {
+    day: &'static Day,
+    part: Part,
+    status: Status,
+    detail: String,
+}
+
+pub fn verify(year: Option<u16>, day: Option<u8>) -> miette::Result<()> {
+    let mut rows = vec![];
+    for entry in registry::select(year, day) {
+        rows.extend(verify_day(entry, &entry.input_path()));
+    }
+    report(&rows)
+}
+
+/// Print the verify table, failing if any row is a FAIL.
+fn report(rows: &[Row]) -> miette::Result<()> {
+    println!("| Year | Day | Part | Status  | Detail");
+    println!("|------|-----|------|---------|-------");
+    for row in rows {
+        let status = match row.status {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Missing => "MISSING",
+        };
+        println!(
+            "| {} | {:>3} | {:>4} | {:<7} | {}",
+            row.day.year,
+            row.day.day,
+            row.part.to_string(),
+            status,
+            row.detail
+        );
+    }
+
+    let failed = rows.iter().filter(|row| row.status == Status::Fail).count();
+    if failed > 0 {
+        return Err(VerifyFailed(failed).into());
+    }
+    Ok(())
+}
+
+/// Rows for both parts of one day solved from `input_path`, checked against the answers stored
+/// beside it. Anything that goes wrong for the day is a FAIL row, so the other days are still
+/// checked.
+fn verify_day(entry: &'static Day, input_path: &Path) -> Vec<Row> {
+    let row = |part, status, detail: String| Row {
+        day: entry,
+        part,
+        status,
+        detail,
+    };
+    let failed = |error: miette::Report| {
+        Part::BOTH
+            .into_iter()
+            .map(|part| row(part, Status::Fail, describe(&error)))
+            .collect()
+    };
+
+    let verdicts = match verify_parts(input_path, |input, parts| {
+        entry.solve_parts(input, parts, false)
+    }) {
+        Ok(Some(verdicts)) => verdicts,
+        Ok(None) => {
+            return Part::BOTH
+                .into_iter()
+                .map(|part| row(part, Status::Missing, "no input".to_string()))
+                .collect();
+        }
+        Err(e) => return failed(e),
+    };
+
+    verdicts
+        .into_iter()
+        .map(|(part, verdict)| match verdict {
+            Verdict::Pass(answer) => row(part, Status::Pass, answer.to_string()),
+            Verdict::Fail { answer, expected } => row(
+                part,
+                Status::Fail,
+                format!("got {answer}, expected {expected}"),
+            ),
+            Verdict::Error(e) => row(part, Status::Fail, describe(&e)),
+            Verdict::Unrecorded(Ok(answer)) => {
+                row(part, Status::Missing, format!("unrecorded {answer}"))
+            }
+            Verdict::Unrecorded(Err(e)) => row(
+                part,
+                Status::Missing,
+                format!("unrecorded, {}", describe(&e)),
+            ),
+        })
+        .collect()
+}
+
+/// An error and its causes on one line, to fit in the table.
+fn describe(error: &miette::Report) -> String {
+    error
+        .chain()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::scratch_dir;
+    use aoc_common::answers::AnswerStore;
+    use std::path::PathBuf;
+    use test_log::test;
+
+    const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    /// Scratch day directory holding `input` and `answers`, if given.
+    fn day_dir(name: &str, input: Option<&str>, answers: Option<&str>) -> PathBuf {
+        let dir = scratch_dir(name);
+        if let Some(input) = input {
+            std::fs::write(dir.join("input.txt"), input).expect("write input");
+        }
+        if let Some(answers) = answers {
+            std::fs::write(AnswerStore::beside(&dir.join("input.txt")), answers)
+                .expect("write answers");
+        }
+        dir
+    }
+
+    fn verify_scratch(name: &str, input: Option<&str>, answers: Option<&str>) -> Vec<Row> {
+        let day = registry::find(2024, 1).expect("day 1 is registered");
+        let dir = day_dir(name, input, answers);
+        verify_day(day, &dir.join("input.txt"))
+    }
+
+    fn failures(rows: &[Row]) -> Option<usize> {
+        report(rows)
+            .err()
+            .map(|e| e.downcast_ref::<VerifyFailed>().expect("verify failure").0)
+    }
+
+    #[test]
+    fn test_verify_pass() {
+        let rows = verify_scratch(
+            "verify-pass",
+            Some(INPUT),
+            Some("[part1]\nanswer = \"11\"\n[part2]\nanswer = \"31\"\n"),
+        );
+        assert!(rows.iter().all(|row| row.status == Status::Pass));
+        assert_eq!("11", rows[0].detail);
+        assert_eq!(None, failures(&rows));
+    }
+
+    #[test]
+    fn test_verify_mismatch() {
+        let rows = verify_scratch(
+            "verify-mismatch",
+            Some(INPUT),
+            Some("[part1]\nanswer = \"12\"\n[part2]\nanswer = \"31\"\n"),
+        );
+        assert_eq!(Status::Fail, rows[0].status);
+        assert_eq!("got 11, expected 12", rows[0].detail);
+        assert_eq!(Status::Pass, rows[1].status);
+        assert_eq!(Some(1), failures(&rows));
+    }
+
+    #[test]
+    fn test_verify_missing_input() {
+        let rows = verify_scratch("verify-no-input", None, None);
+        assert!(rows.iter().all(|row| row.status == Status::Missing));
+        assert_eq!("no input", rows[0].detail);
+        assert_eq!(None, failures(&rows));
+    }
+
+    #[test]
+    fn test_verify_missing_answer() {
+        let rows = verify_scratch(
+            "verify-no-answer",
+            Some(INPUT),
+            Some("[part1]\nanswer = \"11\"\n"),
+        );
+        assert_eq!(Status::Pass, rows[0].status);
+        assert_eq!(Status::Missing, rows[1].status);
+        assert_eq!("unrecorded 31", rows[1].detail);
+        assert_eq!(None, failures(&rows));
+    }
+
+    #[test]
+    fn test_verify_unreadable_input_fails() {
+        let day = registry::find(2024, 1).expect("day 1 is registered");
+        let dir = day_dir("verify-unreadable", None, None);
+        // NOTE: A directory in place of the input can't be read, whoever runs the tests
+        std::fs::create_dir(dir.join("input.txt")).expect("create input dir");
+
+        let rows = verify_day(day, &dir.join("input.txt"));
+
+        assert!(rows.iter().all(|row| row.status == Status::Fail));
+        assert!(rows[0].detail.starts_with("read "));
+        assert_eq!(Some(2), failures(&rows));
+    }
+}
@@ -0,0 +1,14 @@
+use aoc_common::solution::{Part, Solution};
+use {{crate_name}}::*;
+
+aoc_common::bench_main!();
+
+#[divan::bench]
+fn part1() {
+    {{project-name | upper_camel_case}}::solve(divan::black_box(input()), Part::One).unwrap();
+}
+
+#[divan::bench]
+fn part2() {
+    {{project-name | upper_camel_case}}::solve(divan::black_box(input()), Part::Two).unwrap();
+}
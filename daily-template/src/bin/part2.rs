@@ -0,0 +1,15 @@
+use aoc_common::solution::{Part, Solution};
+use {{crate_name}}::{{project-name | upper_camel_case}};
+use miette::Context;
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let file = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
+        .map_err(|e| miette::miette!("Read input file: {e}"))?;
+    let result = {{project-name | upper_camel_case}}::solve(file.as_str(), Part::Two).context("process part 2")?;
+
+    println!("{}", result);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use aoc_common::solution::Solution;
+
+pub mod part1;
+pub mod part2;
+
+pub struct {{project-name | upper_camel_case}};
+
+pub fn parse(input: &str) -> miette::Result<Vec<String>> {
+    Ok(input.lines().map(|line| line.to_string()).collect())
+}
+
+impl Solution for {{project-name | upper_camel_case}} {
+    type Input = Vec<String>;
+    type Answer = u64;
+    type Config = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part1::process(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> miette::Result<Self::Answer> {
+        part2::process(input)
+    }
+}
+
+aoc_common::verify_answers!({{project-name | upper_camel_case}});
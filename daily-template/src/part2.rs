@@ -0,0 +1,20 @@
+#[tracing::instrument(skip(input))]
+pub fn process(input: &[String]) -> miette::Result<u64> {
+    let _ = input;
+    miette::bail!("not solved yet");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use test_log::test;
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "";
+        let error = process(&parse(input)?).unwrap_err();
+        assert_eq!("not solved yet", error.to_string());
+        Ok(())
+    }
+}